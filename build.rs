@@ -0,0 +1,87 @@
+//! Declares a module for every file in `src/solutions`, so adding a day only takes creating its
+//! file. Solutions register themselves with `#[yaaocr::solution]`.
+
+use std::fmt::Write;
+use std::path::Path;
+use std::{env, fs, io};
+
+fn main() -> io::Result<()> {
+    let solutions_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src/solutions");
+    println!("cargo::rerun-if-changed={}", solutions_dir.display());
+
+    let mut code = String::new();
+    declare_modules(&mut code, &solutions_dir, "solutions", 0)?;
+
+    fs::write(
+        Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs"),
+        code,
+    )
+}
+
+/// Rust keywords, which can't be module names.
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+    "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while", "abstract", "become", "box", "do", "final", "macro",
+    "override", "priv", "typeof", "unsized", "virtual", "yield", "try",
+];
+
+/// Declares `dir` as module `name`, with a submodule for each `.rs` file and directory in it,
+/// both sorted. Only the root module is private, solutions aren't part of the library's API.
+/// Files and directories that can't be module names fail the build.
+fn declare_modules(code: &mut String, dir: &Path, name: &str, depth: usize) -> io::Result<()> {
+    let indent = "    ".repeat(depth);
+    let visibility = if depth == 0 { "" } else { "pub " };
+    writeln!(code, "{indent}{visibility}mod {name} {{").unwrap();
+
+    let mut entries = fs::read_dir(dir)?.collect::<io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        let path = entry.path();
+        let is_dir = entry.file_type()?.is_dir();
+        if !is_dir && path.extension().is_none_or(|extension| extension != "rs") {
+            continue;
+        }
+
+        let name = if is_dir {
+            entry.file_name()
+        } else {
+            path.file_stem().unwrap().to_owned()
+        };
+        let name = name.to_string_lossy();
+        if !is_module_name(&name) {
+            println!(
+                "cargo::error='{}' can't be a module, name it like `day09` or `y2015`",
+                path.display()
+            );
+            continue;
+        }
+
+        if is_dir {
+            declare_modules(code, &path, &name, depth + 1)?;
+        } else {
+            writeln!(
+                code,
+                "{indent}    #[path = {:?}]",
+                path.display().to_string()
+            )
+            .unwrap();
+            writeln!(code, "{indent}    pub mod {name};").unwrap();
+        }
+    }
+
+    writeln!(code, "{indent}}}").unwrap();
+    Ok(())
+}
+
+/// Whether `name` is an identifier that isn't a keyword.
+fn is_module_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && name != "_"
+        && !KEYWORDS.contains(&name)
+}
@@ -0,0 +1,131 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::util::ocr::ocr;
+
+/// The answer to one part of a puzzle. Answers are compared by how they're written, so a
+/// number matches the same digits read back from an expected answers file.
+#[derive(Debug, Clone, Eq, Serialize, Deserialize)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+    /// Rows of a picture drawn by the solution, like letters made of `#` and `.`
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    /// A grid from its rows.
+    pub fn grid<S: Into<String>>(rows: impl IntoIterator<Item = S>) -> Self {
+        Answer::Grid(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Reads an answer written with [`fmt::Display`], which spans several lines for grids.
+    pub fn from_written(written: &str) -> Self {
+        if written.contains('\n') {
+            Answer::grid(written.lines())
+        } else {
+            match written.parse::<i128>() {
+                Ok(n) if n.to_string() == written => Answer::Number(n),
+                _ => Answer::Text(written.to_owned()),
+            }
+        }
+    }
+
+    /// Whether two answers are the same. A picture is also the same as text reading the letters
+    /// it shows, which is how Advent of Code gives the answer to a day that draws one.
+    pub fn matches(&self, other: &Answer) -> bool {
+        match (self, other) {
+            _ if self == other => true,
+            (picture, Answer::Text(text)) | (Answer::Text(text), picture)
+                if picture.is_multiline() =>
+            {
+                ocr(&picture.to_string()).is_ok_and(|read| read == *text)
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether the answer spans several lines, and so can't be shown inline.
+    pub fn is_multiline(&self) -> bool {
+        match self {
+            Answer::Number(_) => false,
+            Answer::Text(text) => text.contains('\n'),
+            Answer::Grid(rows) => rows.len() > 1,
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_string() == other.to_string()
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{n}"),
+            Answer::Text(text) => write!(f, "{text}"),
+            Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+/// Returned by a part that isn't implemented yet, or doesn't exist like part 2 of day 25.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NotImplemented;
+
+/// What a part can return: a number, text, an [`Answer`], or [`NotImplemented`].
+pub trait PartAnswer {
+    fn into_answer(self) -> Option<Answer>;
+}
+
+impl PartAnswer for Answer {
+    fn into_answer(self) -> Option<Answer> {
+        Some(self)
+    }
+}
+
+impl PartAnswer for NotImplemented {
+    fn into_answer(self) -> Option<Answer> {
+        None
+    }
+}
+
+impl PartAnswer for String {
+    fn into_answer(self) -> Option<Answer> {
+        Some(Answer::Text(self))
+    }
+}
+
+impl PartAnswer for &str {
+    fn into_answer(self) -> Option<Answer> {
+        Some(Answer::Text(self.to_owned()))
+    }
+}
+
+impl PartAnswer for char {
+    fn into_answer(self) -> Option<Answer> {
+        Some(Answer::Text(self.to_string()))
+    }
+}
+
+macro_rules! number_answers {
+    ($($t:ty),*) => {
+        $(
+            impl PartAnswer for $t {
+                fn into_answer(self) -> Option<Answer> {
+                    Some(match i128::try_from(self) {
+                        Ok(n) => Answer::Number(n),
+                        Err(_) => Answer::Text(self.to_string()),
+                    })
+                }
+            }
+        )*
+    };
+}
+
+number_answers!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
@@ -0,0 +1,159 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use dotenv::dotenv;
+use reqwest::Url;
+use reqwest::blocking::Client;
+use reqwest::cookie::Jar;
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, cache_path};
+
+/// Least time between two requests to Advent of Code, including requests made by earlier runs.
+pub const MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// A page as it was downloaded, kept in the cache directory.
+#[derive(Serialize, Deserialize)]
+struct CachedPage {
+    url: String,
+    /// Seconds since the Unix epoch
+    fetched: u64,
+    body: String,
+}
+
+/// Makes requests to Advent of Code, as its automation guidelines ask: throttled to one every
+/// [`MIN_INTERVAL`], and downloading each page only once unless forced to.
+///
+/// The HTTP client is only built, and the session token only needed, once a request has to go
+/// out.
+#[derive(Default)]
+pub struct AocClient {
+    client: Option<Client>,
+}
+
+impl AocClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets the page at `path` under adventofcode.com, like `2015/day/1/input`. Pages are served
+    /// from the cache unless `force` is set, and cached whenever they're downloaded.
+    pub fn get(&mut self, path: &str, force: bool) -> Result<String, Error> {
+        let url = format!("{BASE_URL}/{path}");
+        let cached_path = cached_page_path(path);
+
+        if !force && let Some(page) = read_cached(&cached_path) {
+            let age = now().saturating_sub(page.fetched);
+            println!(
+                "Using {url} cached {} ago, use --force to download it again",
+                humantime::format_duration(Duration::from_secs(age))
+            );
+            return Ok(page.body);
+        }
+
+        let client = self.client()?;
+        throttle()?;
+        let body = client.get(&url).send()?.error_for_status()?.text()?;
+
+        let page = CachedPage {
+            url,
+            fetched: now(),
+            body,
+        };
+        fs::create_dir_all(cached_path.parent().unwrap())?;
+        let cached = serde_json::to_string(&page).map_err(io::Error::from)?;
+        fs::write(&cached_path, cached)?;
+
+        Ok(page.body)
+    }
+
+    /// Posts a form to `path` under adventofcode.com. Responses to posts aren't cached.
+    pub fn post(&mut self, path: &str, form: &[(&str, &str)]) -> Result<String, Error> {
+        let url = format!("{BASE_URL}/{path}");
+
+        let client = self.client()?;
+        throttle()?;
+        let resp = client.post(&url).form(form).send()?;
+        Ok(resp.error_for_status()?.text()?)
+    }
+
+    fn client(&mut self) -> Result<&Client, Error> {
+        if self.client.is_none() {
+            self.client = Some(http_client()?);
+        }
+        Ok(self.client.as_ref().unwrap())
+    }
+}
+
+fn http_client() -> Result<Client, Error> {
+    dotenv().ok();
+
+    let Ok(session_token) = env::var("AOC_SESSION_TOKEN") else {
+        return Err(Error::MissingSessionToken);
+    };
+
+    let cookie = format!("session={session_token}");
+    let url: Url = BASE_URL.parse().unwrap();
+
+    let jar = Jar::default();
+    jar.add_cookie_str(&cookie, &url);
+
+    Client::builder()
+        .user_agent("github.com/cynicalico/yaaocr cynicalico@pm.me")
+        .cookie_provider(jar.into())
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .map_err(|e| e.into())
+}
+
+/// Waits until [`MIN_INTERVAL`] has passed since the last request, which is recorded in the cache
+/// directory so that it holds across runs, then records this one.
+fn throttle() -> Result<(), Error> {
+    let last_request_path = cache_path().join("last_request");
+
+    let last_request = fs::read_to_string(&last_request_path)
+        .ok()
+        .and_then(|millis| millis.trim().parse().ok())
+        .map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+    if let Some(wait) =
+        last_request.and_then(|last| (last + MIN_INTERVAL).duration_since(SystemTime::now()).ok())
+    {
+        eprintln!(
+            "Waiting {} before the next request to Advent of Code",
+            humantime::format_duration(Duration::from_secs(wait.as_secs_f64().ceil() as u64))
+        );
+        thread::sleep(wait);
+    }
+
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    fs::create_dir_all(cache_path())?;
+    fs::write(last_request_path, millis.to_string())?;
+
+    Ok(())
+}
+
+fn cached_page_path(path: &str) -> PathBuf {
+    cache_path()
+        .join(path.replace('/', "_"))
+        .with_extension("json")
+}
+
+fn read_cached(path: &Path) -> Option<CachedPage> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
@@ -1,32 +1,22 @@
 use std::cmp::PartialEq;
-use std::collections::HashMap;
-use std::env;
-use std::error::Error;
-use std::fmt;
 use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::thread;
+use std::time::{Duration, Instant};
 
-use crate::{check_year_day, filtered_solutions, input_path, puzzle_path};
+use crate::aoc_client::{AocClient, BASE_URL};
+use crate::expected::{Change, Expected};
+use crate::history::{History, Submission};
+use crate::util::ocr::ocr;
+use crate::{
+    Answer, Error, check_year_day, example_expected_path, example_path, expected_path,
+    history_path, input_path, puzzle_path, selected_solutions,
+};
 use clap::ValueEnum;
-use dotenv::dotenv;
+use itertools::Itertools;
 use regex::Regex;
-use reqwest::Url;
-use reqwest::blocking::Client;
-use reqwest::cookie::Jar;
-use scraper::{Html, Selector};
-
-#[derive(Debug)]
-pub struct SessionTokenError;
-
-impl Error for SessionTokenError {}
-
-impl fmt::Display for SessionTokenError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "Failed to find environment variable: 'AOC_SESSION_TOKEN'"
-        )
-    }
-}
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
 pub enum DownloadTarget {
@@ -43,35 +33,37 @@ pub enum PuzzlePart {
     Part2,
 }
 
-pub fn download(
-    year: u32,
-    day: u32,
-    what: DownloadTarget,
-    force: bool,
-) -> Result<(), Box<dyn Error>> {
+impl PuzzlePart {
+    /// The part's number, as Advent of Code calls it in forms.
+    pub fn level(self) -> u8 {
+        match self {
+            PuzzlePart::Part1 => 1,
+            PuzzlePart::Part2 => 2,
+        }
+    }
+}
+
+pub fn download(year: u32, day: u32, what: DownloadTarget, force: bool) -> Result<(), Error> {
     if !check_year_day(year, day) {
-        eprintln!("Invalid year/day combination: {year} {day}");
-        return Ok(());
+        return Err(Error::InvalidPuzzle { year, day });
     }
 
-    let client = http_client()?;
+    let mut client = AocClient::new();
     match what {
-        DownloadTarget::Input => download_input(&client, year, day, force),
-        DownloadTarget::Puzzle => download_puzzle(&client, year, day, force),
+        DownloadTarget::Input => download_input(&mut client, year, day, force),
+        DownloadTarget::Puzzle => download_puzzle(&mut client, year, day, force),
         DownloadTarget::Both => {
-            download_input(&client, year, day, force)?;
-            download_puzzle(&client, year, day, force)
+            download_input(&mut client, year, day, force)?;
+            download_puzzle(&mut client, year, day, force)
         }
     }
 }
 
-fn download_input(client: &Client, year: u32, day: u32, force: bool) -> Result<(), Box<dyn Error>> {
+fn download_input(client: &mut AocClient, year: u32, day: u32, force: bool) -> Result<(), Error> {
     let input_path = input_path(year, day);
 
     if force || !input_path.exists() {
-        let input_url = format!("https://adventofcode.com/{year}/day/{day}/input");
-        let resp = client.get(&input_url).send()?.error_for_status()?;
-        let input = resp.text()?;
+        let input = client.get(&format!("{year}/day/{day}/input"), force)?;
 
         fs::create_dir_all(input_path.parent().unwrap())?;
         fs::write(input_path, input)?;
@@ -85,21 +77,14 @@ fn download_input(client: &Client, year: u32, day: u32, force: bool) -> Result<(
     Ok(())
 }
 
-fn download_puzzle(
-    client: &Client,
-    year: u32,
-    day: u32,
-    force: bool,
-) -> Result<(), Box<dyn Error>> {
+fn download_puzzle(client: &mut AocClient, year: u32, day: u32, force: bool) -> Result<(), Error> {
     let puzzle_path = puzzle_path(year, day);
 
     if force || !puzzle_path.exists() {
-        let puzzle_url = format!("https://adventofcode.com/{year}/day/{day}");
-        let resp = client.get(&puzzle_url).send()?.error_for_status()?;
-        let text = resp.text()?;
+        let text = client.get(&format!("{year}/day/{day}"), force)?;
 
         let html = Html::parse_document(&text);
-        let selector = Selector::parse("article.day-desc")?;
+        let selector = Selector::parse("article.day-desc").unwrap();
 
         let file_content = html.select(&selector).fold(String::new(), |acc, article| {
             if let Ok(md) = htmd::convert(&article.html()) {
@@ -115,6 +100,9 @@ fn download_puzzle(
 
         fs::create_dir_all(puzzle_path.parent().unwrap())?;
         fs::write(puzzle_path, file_content)?;
+
+        record_accepted_answers(year, day, &html)?;
+        save_examples(year, day, &html)?;
     } else {
         println!(
             "{} exists, skipping download, use --force to force",
@@ -125,112 +113,624 @@ fn download_puzzle(
     Ok(())
 }
 
-pub fn submit(year: u32, day: u32, part: PuzzlePart) -> Result<(), Box<dyn Error>> {
-    let answer = {
-        let solutions = filtered_solutions(Some(year), Some(day));
-        if solutions.is_empty() {
-            Err(format!("No solution found for {year} Day {day:02}"))
-        } else if solutions.len() > 1 {
-            Err(format!("Multiple solutions found for {year} Day {day:02}"))
-        } else {
-            let input_path = input_path(year, day);
-            if !input_path.exists() {
-                Err(format!(
-                    "Missing input! Download using `yaaocr download {} {} input`",
-                    year, day
-                ))
-            } else {
-                match fs::read_to_string(&input_path) {
-                    Ok(input) => {
-                        let (part1, part2) = (solutions[0].wrapper)(&input);
+/// Writes the answers shown as "Your puzzle answer was ..." on the puzzle page to the expected
+/// answers file, so that solved days can be verified.
+fn record_accepted_answers(year: u32, day: u32, html: &Html) -> Result<(), Error> {
+    let selector = Selector::parse("main > p").unwrap();
+    let code_selector = Selector::parse("code").unwrap();
+
+    let answers: Vec<Answer> = html
+        .select(&selector)
+        .filter(|p| {
+            p.text()
+                .collect::<String>()
+                .starts_with("Your puzzle answer was")
+        })
+        .filter_map(|p| p.select(&code_selector).next())
+        .map(|code| Answer::from_written(&code.text().collect::<String>()))
+        .collect();
+    if answers.is_empty() {
+        return Ok(());
+    }
+
+    let expected_path = expected_path(year, day);
+    let expected = Expected::read(&expected_path)?;
+    let (part1, part2) = (answers.first(), answers.get(1));
+
+    let changes = expected.changes(part1, part2);
+    if changes.iter().all(|change| *change == Change::Unchanged) {
+        return Ok(());
+    }
+
+    println!("Recorded accepted answers in '{}'", expected_path.display());
+    for (i, change) in changes.iter().enumerate() {
+        if *change != Change::Unchanged {
+            println!("  Part {}: {change}", i + 1);
+        }
+    }
+    expected.with_answers(part1, part2).write(&expected_path)?;
+
+    Ok(())
+}
+
+/// Saves each `<pre><code>` block in the puzzle as a numbered example input. The answer to each
+/// is guessed to be the last highlighted `<code><em>` that follows it, which is written to the
+/// example's expected answers unless it already has one. Part 2 usually goes on with the last
+/// example of part 1 without repeating it, so its answers before its own first example go to
+/// that one. Examples left from an earlier download with more of them are removed.
+fn save_examples(year: u32, day: u32, html: &Html) -> Result<(), Error> {
+    let selector = Selector::parse("article.day-desc").unwrap();
+    let mut examples: Vec<(String, Expected)> = vec![];
+    let mut current = None;
+
+    for (part, article) in html.select(&selector).enumerate() {
+        for element in article.descendants().filter_map(ElementRef::wrap) {
+            let in_pre = element
+                .ancestors()
+                .filter_map(ElementRef::wrap)
+                .any(|e| e.value().name() == "pre");
+            let parent_name = element
+                .parent()
+                .and_then(ElementRef::wrap)
+                .map(|e| e.value().name());
+
+            match (element.value().name(), parent_name) {
+                ("pre", _) => {
+                    let input: String = element.text().collect();
+                    current = match examples.iter().position(|(other, _)| *other == input) {
+                        Some(i) => Some(i),
+                        None => {
+                            examples.push((input, Expected::default()));
+                            Some(examples.len() - 1)
+                        }
+                    };
+                }
+                ("em", Some("code")) | ("code", Some("em")) if !in_pre => {
+                    if let Some(i) = current {
+                        let answer =
+                            Some(Answer::from_written(&element.text().collect::<String>()));
                         match part {
-                            PuzzlePart::Part1 => Ok(part1),
-                            PuzzlePart::Part2 => Ok(part2),
+                            0 => examples[i].1.part1 = answer,
+                            _ => examples[i].1.part2 = answer,
                         }
                     }
-                    Err(err) => Err(format!("Failed to read input: {err}")),
                 }
+                _ => {}
             }
         }
-    }?;
+    }
 
-    let mut params = HashMap::new();
-    match part {
-        PuzzlePart::Part1 => params.insert("level", "1"),
-        PuzzlePart::Part2 => params.insert("level", "2"),
-    };
-    params.insert("answer", &answer);
+    for (i, (input, guess)) in examples.iter().enumerate() {
+        let example = i as u32 + 1;
 
-    println!("Submitting answer: {}", answer);
+        let example_path = example_path(year, day, example);
+        fs::write(&example_path, input)?;
 
-    let client = http_client()?;
-    let submit_url = format!("https://adventofcode.com/{year}/day/{day}/answer");
-    let resp = client
-        .post(&submit_url)
-        .form(&params)
-        .send()?
-        .error_for_status()?;
-    let text = resp.text()?;
+        let expected_path = example_expected_path(year, day, example);
+        let expected = Expected::read(&expected_path)?;
+        let merged = guess.with_answers(expected.part1.as_ref(), expected.part2.as_ref());
+        if merged != expected {
+            merged.write(&expected_path)?;
+        }
+    }
+
+    let stale = (examples.len() as u32 + 1..)
+        .take_while(|&example| example_path(year, day, example).exists())
+        .collect_vec();
+    for &example in &stale {
+        fs::remove_file(example_path(year, day, example))?;
+        let expected_path = example_expected_path(year, day, example);
+        if expected_path.exists() {
+            fs::remove_file(expected_path)?;
+        }
+    }
+    if !stale.is_empty() {
+        println!(
+            "Removed {} examples that are no longer in the puzzle",
+            stale.len()
+        );
+    }
+
+    if !examples.is_empty() {
+        println!(
+            "Saved {} examples, check the guessed answers in '{}'",
+            examples.len(),
+            example_expected_path(year, day, 1)
+                .parent()
+                .unwrap()
+                .display()
+        );
+    }
+
+    Ok(())
+}
+
+/// What Advent of Code said about a submitted answer. Judged outcomes are kept in the submission
+/// history, without what's only useful at the time: how long to wait and unknown pages.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SubmitOutcome {
+    Correct,
+    /// Wrong, and maybe whether it's too low or too high
+    Incorrect {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        hint: Option<Hint>,
+    },
+    /// Not judged, because the last answer was given too recently. `wait` is how long is left
+    /// before another answer can be given, if it could be read.
+    TooRecent {
+        #[serde(skip)]
+        wait: Option<Duration>,
+    },
+    /// Not judged, because the part is already solved or not unlocked yet
+    WrongLevel,
+    /// A response that couldn't be understood, with its HTML
+    Unknown {
+        #[serde(skip)]
+        html: String,
+    },
+}
+
+/// Which side of the right answer a wrong answer is on.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Hint {
+    TooLow,
+    TooHigh,
+}
+
+impl SubmitOutcome {
+    /// Reads the outcome from the page Advent of Code responds to an answer with.
+    pub fn from_response(html: &str) -> Self {
+        let unknown = || SubmitOutcome::Unknown {
+            html: html.to_owned(),
+        };
 
-    let html = Html::parse_document(&text);
-    let selector = Selector::parse("article")?;
+        let document = Html::parse_document(html);
+        let selector = Selector::parse("article").unwrap();
+        let Some(article) = document.select(&selector).next() else {
+            return unknown();
+        };
+        let Ok(md) = htmd::convert(&article.html()) else {
+            return unknown();
+        };
 
-    if let Some(article) = html.select(&selector).next() {
-        let md = htmd::convert(&article.html())?;
         if md.contains("That's the right answer") {
-            print!("✓ That's the right answer!");
-            if part == PuzzlePart::Part1 {
-                print!(" Refreshing puzzle description.");
-                download_puzzle(&client, year, day, true)?;
-            }
-            println!();
+            SubmitOutcome::Correct
         } else if md.contains("That's not the right answer") {
-            if md.contains("too low") {
-                println!("✗ That's not the right answer, it's too low.");
+            let hint = if md.contains("too low") {
+                Some(Hint::TooLow)
             } else if md.contains("too high") {
-                println!("✗ That's not the right answer, it's too high.");
+                Some(Hint::TooHigh)
             } else {
-                println!("✗ That's not the right answer.")
-            }
+                None
+            };
+            SubmitOutcome::Incorrect { hint }
         } else if md.contains("You gave an answer too recently") {
-            print!("⚠ You gave an answer too recently.");
-            if let Some(m) = Regex::new(r"You have \d+s left to wait\.")?.captures(&md) {
-                print!(". {}", m.get(0).unwrap().as_str());
+            SubmitOutcome::TooRecent {
+                wait: time_left_to_wait(&md),
             }
-            println!();
         } else if md.contains("You don't seem to be solving the right level") {
-            println!(
-                "⚠ You don't seem to be solving the right level. Did you already complete it?"
-            );
+            SubmitOutcome::WrongLevel
         } else {
-            eprintln!("⚠ Unexpected response. Saving to bad_response.html");
-            fs::write("bad_response.html", text)?;
+            unknown()
         }
-    } else {
-        eprintln!("⚠ Failed to find 'article' in response. Saving to bad_response.html");
-        fs::write("bad_response.html", text)?;
     }
 
-    Ok(())
+    /// Turns the outcome of submitting `answer` to a part into an error unless it's correct, so
+    /// that the exit code says what Advent of Code said.
+    pub fn into_result(self, year: u32, day: u32, part: u8, answer: &str) -> Result<(), Error> {
+        match self {
+            SubmitOutcome::Correct => Ok(()),
+            SubmitOutcome::Incorrect { hint } => Err(Error::WrongAnswer {
+                year,
+                day,
+                part,
+                answer: answer.to_owned(),
+                hint,
+            }),
+            SubmitOutcome::TooRecent { wait } => Err(Error::TooRecent { wait }),
+            SubmitOutcome::WrongLevel => Err(Error::WrongLevel { year, day, part }),
+            SubmitOutcome::Unknown { .. } => Err(Error::BadResponse(
+                "unrecognized answer to a submission".to_owned(),
+            )),
+        }
+    }
+}
+
+/// Posts an answer to Advent of Code and returns what it said, logging it in the submission
+/// history. Answers the history shows to be wrong aren't posted.
+pub fn post_answer(
+    client: &mut AocClient,
+    year: u32,
+    day: u32,
+    part: PuzzlePart,
+    answer: &str,
+) -> Result<SubmitOutcome, Error> {
+    let history_path = history_path(year, day);
+    check_history(year, day, part, answer)?;
+
+    let level = part.level().to_string();
+    let params = [("level", level.as_str()), ("answer", answer)];
+    let response = client.post(&format!("{year}/day/{day}/answer"), &params)?;
+
+    let outcome = SubmitOutcome::from_response(&response);
+    if !matches!(outcome, SubmitOutcome::Unknown { .. }) {
+        History::append(
+            &history_path,
+            &Submission::new(part.level(), answer, outcome.clone()),
+        )?;
+    }
+
+    Ok(outcome)
+}
+
+/// Fails if the submission history shows that `answer` is wrong.
+fn check_history(year: u32, day: u32, part: PuzzlePart, answer: &str) -> Result<(), Error> {
+    match History::read(&history_path(year, day))?.check(part.level(), answer) {
+        Some(rejection) => Err(Error::KnownWrongAnswer {
+            year,
+            day,
+            part: part.level(),
+            answer: answer.to_owned(),
+            rejection,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Options for [`submit`].
+#[derive(Debug, Clone, Default)]
+pub struct SubmitOptions {
+    /// Submit the answer of this variant instead of the default one
+    pub variant: Option<String>,
+    /// Submit this answer instead of running the solution
+    pub answer: Option<String>,
+    /// If an answer was given too recently, wait and submit it again
+    pub wait: bool,
+    /// Show what would be submitted without submitting it
+    pub dry_run: bool,
+    /// Ask before submitting
+    pub confirm: bool,
 }
 
-fn http_client() -> Result<Client, Box<dyn Error>> {
-    dotenv().ok();
+/// Submits an answer to Advent of Code, computed by the solution unless one is given, and prints
+/// what Advent of Code said about it.
+pub fn submit(year: u32, day: u32, part: PuzzlePart, options: &SubmitOptions) -> Result<(), Error> {
+    if !check_year_day(year, day) {
+        return Err(Error::InvalidPuzzle { year, day });
+    }
 
-    let Ok(session_token) = env::var("AOC_SESSION_TOKEN") else {
-        return Err(SessionTokenError.into());
+    let level = part.level();
+    let answer = match &options.answer {
+        Some(answer) => answer.trim().to_owned(),
+        None => solution_answer(year, day, level, options.variant.as_deref())?,
     };
+    if answer.trim().is_empty() {
+        return Err(Error::EmptyAnswer);
+    }
+    check_history(year, day, part, &answer)?;
+
+    if options.dry_run {
+        println!("Would post to {BASE_URL}/{year}/day/{day}/answer:");
+        println!("  level={level}");
+        println!("  answer={answer}");
+        return Ok(());
+    }
+    if options.confirm
+        && !confirm(&format!(
+            "Submit {answer} for {year} Day {day:02} part {level}?"
+        ))?
+    {
+        return Err(Error::NotConfirmed);
+    }
+
+    let mut client = AocClient::new();
+    loop {
+        println!("Submitting answer: {}", answer);
+        let outcome = post_answer(&mut client, year, day, part, &answer)?;
+        match &outcome {
+            SubmitOutcome::Correct => {
+                println!("✓ That's the right answer! Refreshing puzzle description.");
+                download_puzzle(&mut client, year, day, true)?;
+            }
+            SubmitOutcome::Incorrect { hint } => match hint {
+                Some(Hint::TooLow) => println!("✗ That's not the right answer, it's too low."),
+                Some(Hint::TooHigh) => println!("✗ That's not the right answer, it's too high."),
+                None => println!("✗ That's not the right answer."),
+            },
+            SubmitOutcome::TooRecent { wait } => match wait {
+                Some(wait) => println!(
+                    "⚠ You gave an answer too recently. You have {} left to wait.",
+                    humantime::format_duration(*wait)
+                ),
+                None => println!("⚠ You gave an answer too recently."),
+            },
+            SubmitOutcome::WrongLevel => println!(
+                "⚠ You don't seem to be solving the right level. Did you already complete it?"
+            ),
+            SubmitOutcome::Unknown { html } => {
+                fs::write("bad_response.html", html)?;
+                eprintln!("Saved the response to bad_response.html");
+            }
+        }
+
+        // The history is checked again when resubmitting, in case another run submitted while
+        // this one waited
+        if options.wait
+            && let SubmitOutcome::TooRecent { wait } = outcome
+        {
+            let Some(wait) = wait else {
+                eprintln!("Couldn't read how long to wait, so the answer wasn't resubmitted");
+                return outcome.into_result(year, day, level, &answer);
+            };
+            count_down(wait);
+            continue;
+        }
 
-    let cookie = format!("session={session_token}");
-    let url: Url = "https://adventofcode.com".parse().unwrap();
+        return outcome.into_result(year, day, level, &answer);
+    }
+}
 
-    let jar = Jar::default();
-    jar.add_cookie_str(&cookie, &url);
+/// Runs the solution of a day to get the answer to submit for a part.
+fn solution_answer(year: u32, day: u32, level: u8, variant: Option<&str>) -> Result<String, Error> {
+    let solutions = selected_solutions(Some(year), Some(day), variant);
+    if let (true, Some(variant)) = (solutions.is_empty(), variant) {
+        return Err(Error::NoVariant {
+            year,
+            day,
+            variant: variant.to_owned(),
+        });
+    } else if solutions.is_empty() {
+        return Err(Error::NoSolution { year, day });
+    } else if solutions.len() > 1 {
+        return Err(Error::MultipleSolutions { year, day });
+    }
 
-    Client::builder()
-        .user_agent("github.com/cynicalico/yaaocr cynicalico@pm.me")
-        .cookie_provider(jar.into())
-        .redirect(reqwest::redirect::Policy::none())
-        .build()
-        .map_err(|e| e.into())
+    let input_path = input_path(year, day);
+    if !input_path.exists() {
+        eprintln!("Download input using `yaaocr download {year} {day} input`");
+        return Err(Error::MissingInput(vec![input_path]));
+    }
+
+    let input = fs::read_to_string(&input_path)?;
+    let (part1, part2, _) = solutions[0].run(&input);
+    let answer = if level == 1 { part1 } else { part2 };
+    match answer.map_err(Error::SolutionPanicked)? {
+        None => Err(Error::NotImplemented {
+            year,
+            day,
+            part: level,
+        }),
+        Some(answer) if answer.is_multiline() => {
+            ocr(&answer.to_string()).map_err(|error| Error::GridAnswer {
+                year,
+                day,
+                part: level,
+                error,
+            })
+        }
+        Some(answer) => Ok(answer.to_string()),
+    }
+}
+
+/// Asks a yes or no question on the terminal, taking anything but yes as no. Fails if stdin isn't
+/// a terminal, rather than reading no from a closed or redirected stdin.
+fn confirm(question: &str) -> Result<bool, Error> {
+    if !io::stdin().is_terminal() {
+        return Err(Error::NoTerminal);
+    }
+
+    print!("{question} [y/N] ");
+    io::stdout().flush()?;
+
+    let mut reply = String::new();
+    io::stdin().read_line(&mut reply)?;
+    Ok(matches!(reply.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// How long Advent of Code says is left before another answer can be given, from a response
+/// like "You have 1m 5s left to wait."
+fn time_left_to_wait(md: &str) -> Option<Duration> {
+    let captures = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait")
+        .unwrap()
+        .captures(md)?;
+    let minutes: u64 = captures.get(1).map_or(Ok(0), |m| m.as_str().parse()).ok()?;
+    let seconds: u64 = captures[2].parse().ok()?;
+    Some(Duration::from_secs(minutes * 60 + seconds))
+}
+
+/// Waits for `duration`, showing the time left on a line that's updated every second.
+fn count_down(duration: Duration) {
+    let end = Instant::now() + duration;
+    while let Some(left) = end.checked_duration_since(Instant::now()) {
+        let seconds = Duration::from_secs(left.as_secs_f64().ceil() as u64);
+        eprint!(
+            "\r\x1b[KResubmitting in {}",
+            humantime::format_duration(seconds)
+        );
+        io::stderr().flush().ok();
+        thread::sleep(left.min(Duration::from_secs(1)));
+    }
+    eprintln!("\r\x1b[KResubmitting");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(article: &str) -> String {
+        format!(
+            "<!DOCTYPE html><html><head><title>Day 1 - Advent of Code 2015</title></head><body>\
+             <main><article><p>{article}</p></article></main></body></html>"
+        )
+    }
+
+    #[test]
+    fn reads_correct_answers() {
+        let html = response(
+            "That's the right answer!  You are <span class=\"day-success\">one gold star</span> \
+             closer to powering the weather machine. <a href=\"/2015/day/1#part2\">[Continue to \
+             Part Two]</a>",
+        );
+        assert_eq!(SubmitOutcome::from_response(&html), SubmitOutcome::Correct);
+    }
+
+    #[test]
+    fn reads_wrong_answers_and_their_hints() {
+        let hinted = |hint| {
+            response(&format!(
+                "That's not the right answer; your answer is too {hint}.  If you're stuck, make \
+                 sure you're using the full input data. Please wait one minute before trying \
+                 again. <a href=\"/2015/day/1\">[Return to Day 1]</a>"
+            ))
+        };
+        assert_eq!(
+            SubmitOutcome::from_response(&hinted("low")),
+            SubmitOutcome::Incorrect {
+                hint: Some(Hint::TooLow)
+            }
+        );
+        assert_eq!(
+            SubmitOutcome::from_response(&hinted("high")),
+            SubmitOutcome::Incorrect {
+                hint: Some(Hint::TooHigh)
+            }
+        );
+
+        let html = response(
+            "That's not the right answer.  If you're stuck, make sure you're using the full input \
+             data. Please wait one minute before trying again. <a href=\"/2015/day/1\">[Return to \
+             Day 1]</a>",
+        );
+        assert_eq!(
+            SubmitOutcome::from_response(&html),
+            SubmitOutcome::Incorrect { hint: None }
+        );
+    }
+
+    #[test]
+    fn reads_how_long_to_wait() {
+        let waiting = |left| {
+            response(&format!(
+                "You gave an answer too recently; you have to wait after submitting an answer \
+                 before trying again.  You have {left} left to wait. <a href=\"/2015/day/1\">\
+                 [Return to Day 1]</a>"
+            ))
+        };
+        assert_eq!(
+            SubmitOutcome::from_response(&waiting("4m 32s")),
+            SubmitOutcome::TooRecent {
+                wait: Some(Duration::from_secs(4 * 60 + 32))
+            }
+        );
+        assert_eq!(
+            SubmitOutcome::from_response(&waiting("45s")),
+            SubmitOutcome::TooRecent {
+                wait: Some(Duration::from_secs(45))
+            }
+        );
+
+        let html = response(
+            "You gave an answer too recently; you have to wait after submitting an answer before \
+             trying again. <a href=\"/2015/day/1\">[Return to Day 1]</a>",
+        );
+        assert_eq!(
+            SubmitOutcome::from_response(&html),
+            SubmitOutcome::TooRecent { wait: None }
+        );
+    }
+
+    #[test]
+    fn reads_wrong_levels() {
+        let html = response(
+            "You don't seem to be solving the right level.  Did you already complete it? \
+             <a href=\"/2015/day/1\">[Return to Day 1]</a>",
+        );
+        assert_eq!(
+            SubmitOutcome::from_response(&html),
+            SubmitOutcome::WrongLevel
+        );
+    }
+
+    #[test]
+    fn keeps_responses_it_doesnt_understand() {
+        let html = response("Something went wrong.");
+        assert_eq!(
+            SubmitOutcome::from_response(&html),
+            SubmitOutcome::Unknown { html: html.clone() }
+        );
+
+        let html = "<html><body>No article</body></html>";
+        assert_eq!(
+            SubmitOutcome::from_response(html),
+            SubmitOutcome::Unknown {
+                html: html.to_owned()
+            }
+        );
+    }
+
+    #[test]
+    fn exits_with_the_code_of_the_outcome() {
+        let outcomes = [
+            (SubmitOutcome::Correct, None),
+            (
+                SubmitOutcome::Incorrect {
+                    hint: Some(Hint::TooLow),
+                },
+                Some(3),
+            ),
+            (
+                SubmitOutcome::Incorrect {
+                    hint: Some(Hint::TooHigh),
+                },
+                Some(3),
+            ),
+            (SubmitOutcome::Incorrect { hint: None }, Some(3)),
+            (
+                SubmitOutcome::TooRecent {
+                    wait: Some(Duration::from_secs(45)),
+                },
+                Some(8),
+            ),
+            (SubmitOutcome::TooRecent { wait: None }, Some(8)),
+            (SubmitOutcome::WrongLevel, Some(8)),
+            (
+                SubmitOutcome::Unknown {
+                    html: String::new(),
+                },
+                Some(7),
+            ),
+        ];
+        for (outcome, code) in outcomes {
+            let result = outcome.clone().into_result(2015, 1, 1, "42");
+            assert_eq!(result.err().map(|err| err.exit_code()), code, "{outcome:?}");
+        }
+    }
+
+    #[test]
+    fn keeps_only_the_kind_of_outcome_in_the_history() {
+        let outcomes = [
+            (SubmitOutcome::Correct, r#"{"kind":"correct"}"#),
+            (
+                SubmitOutcome::Incorrect {
+                    hint: Some(Hint::TooLow),
+                },
+                r#"{"kind":"incorrect","hint":"too_low"}"#,
+            ),
+            (
+                SubmitOutcome::Incorrect { hint: None },
+                r#"{"kind":"incorrect"}"#,
+            ),
+            (
+                SubmitOutcome::TooRecent {
+                    wait: Some(Duration::from_secs(45)),
+                },
+                r#"{"kind":"too_recent"}"#,
+            ),
+        ];
+        for (outcome, json) in outcomes {
+            assert_eq!(serde_json::to_string(&outcome).unwrap(), json);
+        }
+    }
 }
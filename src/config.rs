@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+use std::fs;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::{Error, config_path};
+
+/// Settings read from `yaaocr.toml`, all of which are optional.
+///
+/// ```toml
+/// [timeout]
+/// default = "30s"
+/// "2015-04" = "2m"
+/// ```
+#[derive(Debug, Default)]
+pub struct Config {
+    pub default_timeout: Option<Duration>,
+    pub day_timeouts: HashMap<(u32, u32), Duration>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RawConfig {
+    timeout: HashMap<String, String>,
+}
+
+impl Config {
+    /// Reads the config file, or returns the default config if there isn't one.
+    pub fn load() -> Result<Self, Error> {
+        let config_path = config_path();
+        if !config_path.exists() {
+            return Ok(Self::default());
+        }
+
+        let raw: RawConfig = toml::from_str(&fs::read_to_string(&config_path)?).map_err(|err| {
+            Error::Config(format!(
+                "Failed to parse '{}': {err}",
+                config_path.display()
+            ))
+        })?;
+
+        let mut config = Self::default();
+        for (key, value) in raw.timeout {
+            let timeout = humantime::parse_duration(&value).map_err(|err| {
+                Error::Config(format!("Invalid timeout '{value}' for '{key}': {err}"))
+            })?;
+
+            if key == "default" {
+                config.default_timeout = Some(timeout);
+            } else if let Some((Ok(year), Ok(day))) = key
+                .split_once('-')
+                .map(|(year, day)| (year.parse(), day.parse()))
+            {
+                config.day_timeouts.insert((year, day), timeout);
+            } else {
+                return Err(Error::Config(format!(
+                    "Invalid timeout key '{key}', expected 'default' or 'YYYY-DD'"
+                )));
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Timeout for a day, preferring a day-specific value, then `cli_timeout`, then the default.
+    pub fn timeout(&self, year: u32, day: u32, cli_timeout: Option<Duration>) -> Option<Duration> {
+        self.day_timeouts
+            .get(&(year, day))
+            .copied()
+            .or(cli_timeout)
+            .or(self.default_timeout)
+    }
+}
@@ -0,0 +1,286 @@
+use std::path::PathBuf;
+use std::time::Duration;
+use std::{fmt, io};
+
+use crate::Panic;
+use crate::aoc_proxy::Hint;
+use crate::history::Rejection;
+use crate::util::ocr::OcrError;
+
+/// Everything that can go wrong in a `yaaocr` command. Each kind of failure maps to its own
+/// process exit code, see [`Error::exit_code`].
+#[derive(Debug)]
+pub enum Error {
+    /// The year/day combination isn't an Advent of Code puzzle
+    InvalidPuzzle {
+        year: u32,
+        day: u32,
+    },
+    NoSolution {
+        year: u32,
+        day: u32,
+    },
+    MultipleSolutions {
+        year: u32,
+        day: u32,
+    },
+    NoVariant {
+        year: u32,
+        day: u32,
+        variant: String,
+    },
+    /// No solution matching the year and day filters, if any, has the variant
+    NoMatchingVariant {
+        year: Option<u32>,
+        day: Option<u32>,
+        variant: String,
+    },
+    /// The solution returned [`crate::NotImplemented`] for the part, or doesn't have it
+    NotImplemented {
+        year: u32,
+        day: u32,
+        part: u8,
+    },
+    /// The answer to submit is a picture that couldn't be read as letters
+    GridAnswer {
+        year: u32,
+        day: u32,
+        part: u8,
+        error: OcrError,
+    },
+    /// The answer to submit is empty, or only whitespace
+    EmptyAnswer,
+    /// Confirmation to submit was asked for, but stdin isn't a terminal to ask on
+    NoTerminal,
+    /// The answer wasn't confirmed, so it wasn't submitted
+    NotConfirmed,
+    /// The answer to submit is known to be wrong from earlier submissions
+    KnownWrongAnswer {
+        year: u32,
+        day: u32,
+        part: u8,
+        answer: String,
+        rejection: Rejection,
+    },
+    /// Advent of Code said the submitted answer is wrong
+    WrongAnswer {
+        year: u32,
+        day: u32,
+        part: u8,
+        answer: String,
+        hint: Option<Hint>,
+    },
+    /// Advent of Code didn't judge the answer, because one was given too recently. `wait` is how
+    /// long is left before another can be given, if it could be read.
+    TooRecent {
+        wait: Option<Duration>,
+    },
+    /// Advent of Code didn't judge the answer, because the part is already solved or not unlocked
+    WrongLevel {
+        year: u32,
+        day: u32,
+        part: u8,
+    },
+    /// A solution panicked while computing an answer to submit
+    SolutionPanicked(Panic),
+    /// Some days gave a wrong answer, panicked or timed out
+    Verification {
+        incorrect: usize,
+        panicked: usize,
+        timed_out: usize,
+    },
+    /// Variants of some days gave different answers, or some variants failed to run
+    Comparison {
+        disagreeing: usize,
+        failed: usize,
+    },
+    MissingInput(Vec<PathBuf>),
+    MissingSessionToken,
+    Network(reqwest::Error),
+    /// Advent of Code responded with something we didn't expect
+    BadResponse(String),
+    Config(String),
+    Io(io::Error),
+    Output(String),
+}
+
+impl Error {
+    /// 1 for general failures, 2 for bad arguments (like clap), 3 for wrong, panicking or timed
+    /// out solutions, 4 for missing inputs, 5 for network errors, 6 for a missing session token,
+    /// 7 for unexpected responses from Advent of Code and 8 for answers it didn't judge.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Error::Config(_)
+            | Error::Io(_)
+            | Error::Output(_)
+            | Error::SolutionPanicked(_)
+            | Error::NotConfirmed
+            | Error::GridAnswer { .. } => 1,
+            Error::InvalidPuzzle { .. }
+            | Error::NoSolution { .. }
+            | Error::MultipleSolutions { .. }
+            | Error::NoVariant { .. }
+            | Error::NoMatchingVariant { .. }
+            | Error::NotImplemented { .. }
+            | Error::EmptyAnswer
+            | Error::NoTerminal => 2,
+            Error::Verification { .. }
+            | Error::Comparison { .. }
+            | Error::KnownWrongAnswer { .. }
+            | Error::WrongAnswer { .. } => 3,
+            Error::MissingInput(_) => 4,
+            Error::Network(_) => 5,
+            Error::MissingSessionToken => 6,
+            Error::BadResponse(_) => 7,
+            Error::TooRecent { .. } | Error::WrongLevel { .. } => 8,
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Network(err) => Some(err),
+            Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidPuzzle { year, day } => {
+                write!(f, "Invalid year/day combination: {year} {day}")
+            }
+            Error::NoSolution { year, day } => {
+                write!(f, "No solution found for {year} Day {day:02}")
+            }
+            Error::MultipleSolutions { year, day } => {
+                write!(f, "Multiple solutions found for {year} Day {day:02}")
+            }
+            Error::NoVariant { year, day, variant } => {
+                write!(f, "No variant '{variant}' found for {year} Day {day:02}")
+            }
+            Error::NoMatchingVariant { year, day, variant } => {
+                write!(f, "No variant '{variant}' found")?;
+                match (year, day) {
+                    (Some(year), Some(day)) => write!(f, " for {year} Day {day:02}"),
+                    (Some(year), None) => write!(f, " for {year}"),
+                    (None, Some(day)) => write!(f, " for Day {day:02}"),
+                    (None, None) => Ok(()),
+                }
+            }
+            Error::NotImplemented { year, day, part } => {
+                write!(f, "{year} Day {day:02} part {part} is not implemented")
+            }
+            Error::GridAnswer {
+                year,
+                day,
+                part,
+                error,
+            } => write!(
+                f,
+                "{year} Day {day:02} part {part} is a picture that can't be read as letters: {error}"
+            ),
+            Error::EmptyAnswer => write!(f, "Not submitting an empty answer"),
+            Error::NoTerminal => write!(
+                f,
+                "Can't ask to confirm the answer without a terminal, pass --yes to submit it"
+            ),
+            Error::NotConfirmed => write!(f, "Not submitted"),
+            Error::KnownWrongAnswer {
+                year,
+                day,
+                part,
+                answer,
+                rejection,
+            } => write!(
+                f,
+                "Not submitting {answer} for {year} Day {day:02} part {part}, {rejection}"
+            ),
+            Error::WrongAnswer {
+                year,
+                day,
+                part,
+                answer,
+                hint,
+            } => {
+                write!(f, "{answer} is wrong for {year} Day {day:02} part {part}")?;
+                match hint {
+                    Some(Hint::TooLow) => write!(f, ", it's too low"),
+                    Some(Hint::TooHigh) => write!(f, ", it's too high"),
+                    None => Ok(()),
+                }
+            }
+            Error::TooRecent { wait: Some(wait) } => write!(
+                f,
+                "Answer not judged, wait {} before giving another or use --wait",
+                humantime::format_duration(*wait)
+            ),
+            Error::TooRecent { wait: None } => {
+                write!(f, "Answer not judged, because one was given too recently")
+            }
+            Error::WrongLevel { year, day, part } => write!(
+                f,
+                "Answer not judged, {year} Day {day:02} part {part} is already solved or not unlocked yet"
+            ),
+            Error::SolutionPanicked(panic) => write!(f, "Solution {panic}"),
+            Error::Verification {
+                incorrect,
+                panicked,
+                timed_out,
+            } => write!(
+                f,
+                "Failed days: {incorrect} incorrect, {panicked} panicked, {timed_out} timed out"
+            ),
+            Error::Comparison {
+                disagreeing,
+                failed,
+            } => write!(
+                f,
+                "Failed comparisons: {disagreeing} disagreeing, {failed} failed to run"
+            ),
+            Error::MissingInput(paths) => {
+                write!(f, "Missing input")?;
+                for (i, path) in paths.iter().enumerate() {
+                    write!(f, "{} '{}'", if i == 0 { "" } else { "," }, path.display())?;
+                }
+                Ok(())
+            }
+            Error::MissingSessionToken => {
+                write!(
+                    f,
+                    "Failed to find environment variable: 'AOC_SESSION_TOKEN'"
+                )
+            }
+            Error::Network(err) => write!(f, "Request failed: {err}"),
+            Error::BadResponse(msg) => write!(f, "Unexpected response: {msg}"),
+            Error::Config(msg) => write!(f, "{msg}"),
+            Error::Io(err) => write!(f, "{err}"),
+            Error::Output(msg) => write!(f, "Failed to write report: {msg}"),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_status() {
+            Error::BadResponse(err.to_string())
+        } else {
+            Error::Network(err)
+        }
+    }
+}
+
+impl From<csv::Error> for Error {
+    fn from(err: csv::Error) -> Self {
+        Error::Output(err.to_string())
+    }
+}
@@ -0,0 +1,124 @@
+use std::path::Path;
+use std::{fmt, fs, io};
+
+use crate::Answer;
+
+/// Marks the start and end of an answer spanning several lines.
+const FENCE: &str = "```";
+
+/// Known answers for a day, stored one part per line. An empty line means the answer to that
+/// part isn't known yet. An answer spanning several lines, like a grid, is written between two
+/// ```` ``` ```` lines instead:
+///
+/// ````text
+/// ```
+/// #..#
+/// ####
+/// ```
+/// 42
+/// ````
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Expected {
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+/// What recording a new answer does to the expected answer of one part.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Change<'a> {
+    Unchanged,
+    Added(&'a Answer),
+    Replaced { old: &'a Answer, new: &'a Answer },
+}
+
+impl fmt::Display for Change<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Pictures go on the lines below, indented under the part they're for
+        let rows = |answer: &Answer| {
+            answer
+                .to_string()
+                .lines()
+                .map(|row| format!("\n    {row}"))
+                .collect::<String>()
+        };
+        match self {
+            Change::Unchanged => write!(f, "unchanged"),
+            Change::Added(new) if new.is_multiline() => write!(f, "+{}", rows(new)),
+            Change::Added(new) => write!(f, "+ {new}"),
+            Change::Replaced { old, new } if old.is_multiline() || new.is_multiline() => {
+                write!(f, "replaced{}\n    →{}", rows(old), rows(new))
+            }
+            Change::Replaced { old, new } => write!(f, "{old} → {new}"),
+        }
+    }
+}
+
+impl Expected {
+    /// Reads the answers in `path`, or returns no answers if the file doesn't exist.
+    pub fn read(path: &Path) -> io::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)?;
+        let mut lines = content.lines();
+        let mut next_answer = || match lines.next() {
+            None | Some("") => None,
+            Some(FENCE) => Some(Answer::grid(
+                lines.by_ref().take_while(|line| *line != FENCE),
+            )),
+            Some(line) => Some(Answer::from_written(line)),
+        };
+
+        Ok(Self {
+            part1: next_answer(),
+            part2: next_answer(),
+        })
+    }
+
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let written = |answer: Option<&Answer>| match answer {
+            Some(answer) if answer.is_multiline() => format!("{FENCE}\n{answer}\n{FENCE}\n"),
+            Some(answer) => format!("{answer}\n"),
+            None => "\n".to_owned(),
+        };
+
+        let mut content = written(self.part1.as_ref());
+        if let Some(part2) = &self.part2 {
+            content += &written(Some(part2));
+        }
+
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, content)
+    }
+
+    /// These answers, with `part1` and `part2` replacing them where given. An answer is kept
+    /// when the new one matches it, so a picture isn't replaced by the letters it shows.
+    pub fn with_answers(&self, part1: Option<&Answer>, part2: Option<&Answer>) -> Self {
+        let with = |old: &Option<Answer>, new: Option<&Answer>| match (old, new) {
+            (Some(old), Some(new)) if old.matches(new) => Some(old.clone()),
+            (old, new) => new.cloned().or(old.clone()),
+        };
+        Self {
+            part1: with(&self.part1, part1),
+            part2: with(&self.part2, part2),
+        }
+    }
+
+    /// Changes to part 1 and part 2 if `part1` and `part2` were recorded. Parts without a new
+    /// answer are left alone.
+    pub fn changes<'a>(
+        &'a self,
+        part1: Option<&'a Answer>,
+        part2: Option<&'a Answer>,
+    ) -> [Change<'a>; 2] {
+        [(self.part1.as_ref(), part1), (self.part2.as_ref(), part2)].map(|(old, new)| {
+            match (old, new) {
+                (_, None) => Change::Unchanged,
+                (Some(old), Some(new)) if old.matches(new) => Change::Unchanged,
+                (None, Some(new)) => Change::Added(new),
+                (Some(old), Some(new)) => Change::Replaced { old, new },
+            }
+        })
+    }
+}
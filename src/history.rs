@@ -0,0 +1,237 @@
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::aoc_proxy::{Hint, SubmitOutcome};
+
+/// One answer given to Advent of Code.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Submission {
+    pub part: u8,
+    pub answer: String,
+    pub outcome: SubmitOutcome,
+    /// Seconds since the Unix epoch
+    pub submitted: u64,
+}
+
+impl Submission {
+    pub fn new(part: u8, answer: &str, outcome: SubmitOutcome) -> Self {
+        Self {
+            part,
+            answer: answer.to_owned(),
+            outcome,
+            submitted: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        }
+    }
+}
+
+/// Why an answer is known to be wrong without submitting it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rejection {
+    /// The same answer was submitted before and judged wrong, with the hint it was given
+    AlreadyRejected(Option<Hint>),
+    /// At or below an answer that was too low
+    TooLow { bound: i128 },
+    /// At or above an answer that was too high
+    TooHigh { bound: i128 },
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Rejection::AlreadyRejected(Some(Hint::TooLow)) => write!(f, "it was already too low"),
+            Rejection::AlreadyRejected(Some(Hint::TooHigh)) => {
+                write!(f, "it was already too high")
+            }
+            Rejection::AlreadyRejected(None) => write!(f, "it was already rejected"),
+            Rejection::TooLow { bound } => write!(f, "{bound} was already too low"),
+            Rejection::TooHigh { bound } => write!(f, "{bound} was already too high"),
+        }
+    }
+}
+
+/// Every answer given for a day and what Advent of Code said about it, stored as one JSON
+/// object per line so that submissions can be appended.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct History {
+    pub submissions: Vec<Submission>,
+}
+
+impl History {
+    /// Reads the submissions in `path`, or returns no submissions if the file doesn't exist.
+    pub fn read(path: &Path) -> io::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let submissions = fs::read_to_string(path)?
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                serde_json::from_str(line).map_err(|err| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "Failed to read line {} of '{}': {err}",
+                            i + 1,
+                            path.display()
+                        ),
+                    )
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        Ok(Self { submissions })
+    }
+
+    /// Adds a submission to the end of the history in `path`.
+    pub fn append(path: &Path, submission: &Submission) -> io::Result<()> {
+        fs::create_dir_all(path.parent().unwrap())?;
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", serde_json::to_string(submission)?)
+    }
+
+    /// Checks `answer` to `part` against the answers that were judged wrong before, returning
+    /// why it's wrong too if it is. Numbers are also checked against the answers that were too
+    /// low or too high.
+    pub fn check(&self, part: u8, answer: &str) -> Option<Rejection> {
+        let judged: Vec<&Submission> = self
+            .submissions
+            .iter()
+            .filter(|submission| submission.part == part)
+            .collect();
+
+        let rejected = judged
+            .iter()
+            .find_map(|submission| match submission.outcome {
+                SubmitOutcome::Incorrect { hint } if submission.answer == answer => Some(hint),
+                _ => None,
+            });
+        if let Some(hint) = rejected {
+            return Some(Rejection::AlreadyRejected(hint));
+        }
+
+        let number: i128 = answer.parse().ok()?;
+        let bound = |side| {
+            judged
+                .iter()
+                .filter(move |submission| {
+                    submission.outcome == SubmitOutcome::Incorrect { hint: Some(side) }
+                })
+                .filter_map(|submission| submission.answer.parse::<i128>().ok())
+        };
+        if let Some(bound) = bound(Hint::TooLow).max().filter(|&bound| number <= bound) {
+            Some(Rejection::TooLow { bound })
+        } else {
+            bound(Hint::TooHigh)
+                .min()
+                .filter(|&bound| number >= bound)
+                .map(|bound| Rejection::TooHigh { bound })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOO_LOW: SubmitOutcome = SubmitOutcome::Incorrect {
+        hint: Some(Hint::TooLow),
+    };
+    const TOO_HIGH: SubmitOutcome = SubmitOutcome::Incorrect {
+        hint: Some(Hint::TooHigh),
+    };
+    const INCORRECT: SubmitOutcome = SubmitOutcome::Incorrect { hint: None };
+
+    fn history(submissions: &[(u8, &str, SubmitOutcome)]) -> History {
+        History {
+            submissions: submissions
+                .iter()
+                .map(|(part, answer, outcome)| Submission::new(*part, answer, outcome.clone()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn rejects_repeated_wrong_answers() {
+        let history = history(&[
+            (1, "abc", INCORRECT),
+            (1, "42", TOO_LOW),
+            (1, "70", SubmitOutcome::TooRecent { wait: None }),
+        ]);
+        assert_eq!(
+            history.check(1, "abc"),
+            Some(Rejection::AlreadyRejected(None))
+        );
+        assert_eq!(
+            history.check(1, "42"),
+            Some(Rejection::AlreadyRejected(Some(Hint::TooLow)))
+        );
+        // Answers that weren't judged can be given again
+        assert_eq!(history.check(1, "70"), None);
+    }
+
+    #[test]
+    fn rejects_answers_outside_the_bounds() {
+        let history = history(&[
+            (1, "10", TOO_LOW),
+            (1, "20", TOO_LOW),
+            (1, "100", TOO_HIGH),
+            (1, "90", TOO_HIGH),
+        ]);
+        assert_eq!(history.check(1, "5"), Some(Rejection::TooLow { bound: 20 }));
+        assert_eq!(
+            history.check(1, "20"),
+            Some(Rejection::AlreadyRejected(Some(Hint::TooLow)))
+        );
+        assert_eq!(
+            history.check(1, "15"),
+            Some(Rejection::TooLow { bound: 20 })
+        );
+        assert_eq!(history.check(1, "21"), None);
+        assert_eq!(history.check(1, "89"), None);
+        assert_eq!(
+            history.check(1, "95"),
+            Some(Rejection::TooHigh { bound: 90 })
+        );
+        assert_eq!(
+            history.check(1, "1000"),
+            Some(Rejection::TooHigh { bound: 90 })
+        );
+    }
+
+    #[test]
+    fn only_bounds_numbers() {
+        let history = history(&[(1, "10", TOO_LOW), (1, "abc", TOO_HIGH)]);
+        assert_eq!(history.check(1, "abd"), None);
+        assert_eq!(history.check(1, "ABC"), None);
+        assert_eq!(history.check(1, "11"), None);
+    }
+
+    #[test]
+    fn checks_each_part_on_its_own() {
+        let history = history(&[(1, "10", TOO_LOW), (1, "abc", INCORRECT)]);
+        assert_eq!(history.check(2, "5"), None);
+        assert_eq!(history.check(2, "abc"), None);
+    }
+
+    #[test]
+    fn reports_the_line_of_bad_submissions() {
+        let path =
+            std::env::temp_dir().join(format!("yaaocr-history-{}.jsonl", std::process::id()));
+        History::append(&path, &Submission::new(1, "10", TOO_LOW)).unwrap();
+        fs::write(&path, fs::read_to_string(&path).unwrap() + "\nnot json\n").unwrap();
+
+        let err = History::read(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert!(err.to_string().contains("line 3"), "{err}");
+    }
+}
@@ -1,16 +1,71 @@
 #![feature(iter_array_chunks)]
 
-use crate::util::parse::ParseOps;
+extern crate self as yaaocr;
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::ops::AddAssign;
+use std::panic::{self, AssertUnwindSafe};
 use std::path::{Path, PathBuf};
+use std::sync::Once;
+use std::time::{Duration, Instant};
 
+pub mod answer;
+pub mod aoc_client;
 pub mod aoc_proxy;
+pub mod config;
+pub mod error;
+pub mod expected;
+pub mod history;
 pub mod runner;
+pub mod scaffold;
+pub mod watch;
+
+pub use answer::{Answer, NotImplemented, PartAnswer};
+pub use error::Error;
+#[doc(hidden)]
+pub use inventory;
+pub use util::ocr::{OcrError, ocr, ocr_bitmap};
+/// Registers a solution. On an `impl` of [`Solution`], it registers that implementation. On
+/// `parse`, or on an inline module containing it, it registers the whole day with the `part1`
+/// and `part2` functions next to `parse`:
+///
+/// ```ignore
+/// #[yaaocr::solution(year = 2015, day = 7)]
+/// pub fn parse(input: &str) -> Circuit { ... }
+/// ```
+///
+/// On any other function it registers a single part, parsing the input separately for each part.
+/// Alternative implementations of a day are registered with a name, like `variant = "dp"`.
+///
+/// Part functions are named `part1` or `part2`, or say which part they are with `part = 2`.
+/// They parse the input with `parse` from the same module, or the function given with
+/// `parse = parse_wires`.
+pub use yaaocr_macros::solution;
 
 pub fn check_year_day(year: u32, day: u32) -> bool {
     ((2015..2025).contains(&year) && (1..=25).contains(&day))
         || ((2025..).contains(&year) && (1..=12).contains(&day))
 }
 
+pub fn config_path() -> PathBuf {
+    PathBuf::from("yaaocr.toml")
+}
+
+pub fn cache_path() -> PathBuf {
+    PathBuf::from("cache")
+}
+
+pub fn solution_path(year: u32, day: u32) -> PathBuf {
+    Path::new("src")
+        .join("solutions")
+        .join(format!("y{year}"))
+        .join(format!("day{day:02}"))
+        .with_extension("rs")
+}
+
 pub fn input_path(year: u32, day: u32) -> PathBuf {
     Path::new("input")
         .join(format!("{year}"))
@@ -26,6 +81,29 @@ pub fn expected_path(year: u32, day: u32) -> PathBuf {
         .with_extension("txt")
 }
 
+pub fn example_path(year: u32, day: u32, example: u32) -> PathBuf {
+    Path::new("puzzle")
+        .join(format!("{year}"))
+        .join(format!("day{day:02}-example{example}"))
+        .with_extension("txt")
+}
+
+pub fn example_expected_path(year: u32, day: u32, example: u32) -> PathBuf {
+    Path::new("puzzle")
+        .join(format!("{year}"))
+        .join("expected")
+        .join(format!("day{day:02}-example{example}"))
+        .with_extension("txt")
+}
+
+pub fn history_path(year: u32, day: u32) -> PathBuf {
+    Path::new("puzzle")
+        .join(format!("{year}"))
+        .join("history")
+        .join(format!("day{day:02}"))
+        .with_extension("jsonl")
+}
+
 pub fn puzzle_path(year: u32, day: u32) -> PathBuf {
     Path::new("puzzle")
         .join(format!("{year}"))
@@ -33,117 +111,327 @@ pub fn puzzle_path(year: u32, day: u32) -> PathBuf {
         .with_extension("md")
 }
 
-pub struct Solution {
+/// Time spent in each phase of a solution.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part1 + self.part2
+    }
+}
+
+impl AddAssign for Timings {
+    fn add_assign(&mut self, rhs: Self) {
+        self.parse += rhs.parse;
+        self.part1 += rhs.part1;
+        self.part2 += rhs.part2;
+    }
+}
+
+/// A panic caught while running part of a solution.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Panic {
+    pub message: String,
+    pub location: Option<String>,
+}
+
+impl fmt::Display for Panic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "panicked at {location}: {}", self.message),
+            None => write!(f, "panicked: {}", self.message),
+        }
+    }
+}
+
+/// The answer to one part of a puzzle, `None` if the part isn't implemented, or the panic that
+/// happened while computing it. If parsing panics, both parts hold that panic.
+pub type PartResult = Result<Option<Answer>, Panic>;
+
+thread_local! {
+    static CAUGHT_PANIC: RefCell<Option<Panic>> = const { RefCell::new(None) };
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f`, turning a panic into an error instead of unwinding further. The default panic
+/// message is suppressed for panics caught this way.
+pub fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, Panic> {
+    static INSTALL_HOOK: Once = Once::new();
+    INSTALL_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !CATCHING.get() {
+                default_hook(info);
+                return;
+            }
+
+            let payload = info.payload();
+            let message = if let Some(s) = payload.downcast_ref::<&str>() {
+                s.to_string()
+            } else if let Some(s) = payload.downcast_ref::<String>() {
+                s.clone()
+            } else {
+                "Box<dyn Any>".to_owned()
+            };
+            let location = info.location().map(|l| l.to_string());
+
+            CAUGHT_PANIC.set(Some(Panic { message, location }));
+        }));
+    });
+
+    let was_catching = CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(was_catching);
+
+    result.map_err(|_| {
+        CAUGHT_PANIC.take().unwrap_or_else(|| Panic {
+            message: "unknown panic".to_owned(),
+            location: None,
+        })
+    })
+}
+
+/// A solution to a day's puzzle, with its parsed input and answers typed. Register an
+/// implementation by putting [`solution`] on the `impl`:
+///
+/// ```ignore
+/// pub struct Day07;
+///
+/// #[yaaocr::solution(year = 2015, day = 7)]
+/// impl Solution for Day07 {
+///     type Input<'a> = HashMap<&'a str, Wire<'a>>;
+///     type Answer1 = u16;
+///     type Answer2 = u16;
+///     ...
+/// }
+/// ```
+pub trait Solution {
+    /// The parsed input, which may borrow from the raw input.
+    type Input<'a>;
+    type Answer1: PartAnswer;
+    type Answer2: PartAnswer;
+
+    fn parse(input: &str) -> Self::Input<'_>;
+    fn part1(input: &Self::Input<'_>) -> Self::Answer1;
+    fn part2(input: &Self::Input<'_>) -> Self::Answer2;
+}
+
+/// Runs `S` on `input`, timing each phase and catching panics.
+pub fn run_solution<S: Solution>(input: &str) -> (PartResult, PartResult, Timings) {
+    run_day(input, S::parse, S::part1, S::part2)
+}
+
+/// Runs a whole day, parsing the input once for both parts.
+pub type DayWrapper = fn(&str) -> (PartResult, PartResult, Timings);
+
+/// Runs one part, returning its answer and how long parsing and solving took.
+pub type PartWrapper = fn(&str) -> (PartResult, Duration, Duration);
+
+/// A day or a single part registered with [`solution`]. Registrations are combined into
+/// [`RegisteredSolution`]s by [`filtered_solutions`].
+#[doc(hidden)]
+pub struct Registration {
     pub year: u32,
     pub day: u32,
-    pub wrapper: fn(&str) -> (String, String),
+    pub variant: Option<&'static str>,
+    pub wrapper: Registered,
 }
 
-pub fn filtered_solutions(year: Option<u32>, day: Option<u32>) -> Vec<Solution> {
-    std::iter::empty()
-        .chain(y2015())
-        .filter(|s| year.is_none_or(|y| y == s.year))
-        .filter(|s| day.is_none_or(|d| d == s.day))
-        .collect()
+#[doc(hidden)]
+pub enum Registered {
+    Day(DayWrapper),
+    Part1(PartWrapper),
+    Part2(PartWrapper),
 }
 
-mod util {
-    pub mod bits;
-    pub mod integer;
-    pub mod parse;
+inventory::collect!(Registration);
+
+#[derive(Clone, Copy)]
+pub enum Wrapper {
+    Day(DayWrapper),
+    /// Parts registered on their own, each parsing the input itself. A part that wasn't
+    /// registered isn't implemented.
+    Parts(Option<PartWrapper>, Option<PartWrapper>),
+}
+
+/// Stack size for threads that run solutions. Solutions are written to run on the main thread,
+/// and some keep large arrays on the stack.
+pub const SOLUTION_STACK_SIZE: usize = 64 * 1024 * 1024;
+
+/// A registered solution for a day, as returned by [`filtered_solutions`].
+#[derive(Clone, Copy)]
+pub struct RegisteredSolution {
+    pub year: u32,
+    pub day: u32,
+    /// Name of an alternative implementation, `None` for the default one
+    pub variant: Option<&'static str>,
+    pub wrapper: Wrapper,
+}
+
+impl RegisteredSolution {
+    pub fn run(&self, input: &str) -> (PartResult, PartResult, Timings) {
+        match self.wrapper {
+            Wrapper::Day(wrapper) => wrapper(input),
+            Wrapper::Parts(part1, part2) => {
+                let run = |part: Option<PartWrapper>| match part {
+                    Some(wrapper) => wrapper(input),
+                    None => (Ok(None), Duration::ZERO, Duration::ZERO),
+                };
+                let (part1, parse1, part1_elapsed) = run(part1);
+                let (part2, parse2, part2_elapsed) = run(part2);
+
+                (
+                    part1,
+                    part2,
+                    Timings {
+                        parse: parse1 + parse2,
+                        part1: part1_elapsed,
+                        part2: part2_elapsed,
+                    },
+                )
+            }
+        }
+    }
 }
 
-macro_rules! make_solutions {
-    ($year:tt $($day:tt),*) => {
-        fn $year() -> Vec<Solution> {
-            vec![$(
-                Solution {
-                    year: stringify!($year).unsigned(),
-                    day: stringify!($day).unsigned(),
-                    wrapper: |input: &str| {
-                        use solutions::$year::$day::*;
-
-                        let parsed_input = parse(&input);
-                        let part1 = part1(&parsed_input).to_string();
-                        let part2 = part2(&parsed_input).to_string();
-
-                        (part1, part2)
-                    }
-                }
-            ,)*]
+/// Registered solutions for `year` and `day` if given, with every variant of a day, sorted by
+/// day and then variant with the default variant first. Parts registered on their own are
+/// paired up into one solution per variant.
+pub fn filtered_solutions(year: Option<u32>, day: Option<u32>) -> Vec<RegisteredSolution> {
+    let registrations = inventory::iter::<Registration>
+        .into_iter()
+        .filter(|r| year.is_none_or(|y| y == r.year))
+        .filter(|r| day.is_none_or(|d| d == r.day))
+        .into_group_map_by(|r| (r.year, r.day, r.variant));
+
+    let mut solutions = vec![];
+    for ((year, day, variant), registrations) in
+        registrations.into_iter().sorted_by_key(|(k, _)| *k)
+    {
+        let mut parts1 = vec![];
+        let mut parts2 = vec![];
+        for registration in registrations {
+            match registration.wrapper {
+                Registered::Day(wrapper) => solutions.push(RegisteredSolution {
+                    year,
+                    day,
+                    variant,
+                    wrapper: Wrapper::Day(wrapper),
+                }),
+                Registered::Part1(wrapper) => parts1.push(wrapper),
+                Registered::Part2(wrapper) => parts2.push(wrapper),
+            }
+        }
+
+        for i in 0..parts1.len().max(parts2.len()) {
+            solutions.push(RegisteredSolution {
+                year,
+                day,
+                variant,
+                wrapper: Wrapper::Parts(parts1.get(i).copied(), parts2.get(i).copied()),
+            });
         }
     }
+
+    solutions
 }
 
-/*[[[cog
-import os
-import re
-
-SOLUTIONS_DIR = os.path.join("src", "solutions")
-
-year_re = re.compile(r"^y(\d{4})$")
-day_re  = re.compile(r"^day(\d{2})\.rs$")
-
-def year_key(yname: str) -> int:
-    m = year_re.match(yname)
-    return int(m.group(1)) if m else 0
-
-def chunked(xs, n):
-    for i in range(0, len(xs), n):
-        yield xs[i:i+n]
-
-years = []
-for entry in os.listdir(SOLUTIONS_DIR):
-    if year_re.match(entry) and os.path.isdir(os.path.join(SOLUTIONS_DIR, entry)):
-        years.append(entry)
-
-cog.outl("mod solutions {")
-for year in sorted(years, key=year_key):
-    year_path = os.path.join(SOLUTIONS_DIR, year)
-    days = []
-    for fname in os.listdir(year_path):
-        m = day_re.match(fname)
-        if m and os.path.isfile(os.path.join(year_path, fname)):
-            days.append(m.group(1))
-
-    cog.outl(f"    pub mod {year} {{")
-    for dd in sorted(set(days)):
-        cog.outl(f"        pub mod day{dd};")
-    cog.outl("    }")
-cog.outl("}")
-
-for year in sorted(years, key=year_key):
-    year_path = os.path.join(SOLUTIONS_DIR, year)
-    days = []
-    for fname in os.listdir(year_path):
-        m = day_re.match(fname)
-        if m and os.path.isfile(os.path.join(year_path, fname)):
-            days.append(f"day{m.group(1)}")
-
-    days = sorted(set(days))
-
-    cog.outl()
-    cog.outl(f"make_solutions!({year}")
-    for group in chunked(days, 5):
-        cog.out("    " + ", ".join(group))
-        cog.outl(");" if group == days[-len(group):] else ",")
-]]]*/
-mod solutions {
-    pub mod y2015 {
-        pub mod day01;
-        pub mod day02;
-        pub mod day03;
-        pub mod day04;
-        pub mod day05;
-        pub mod day06;
-        pub mod day07;
-        pub mod day08;
-        pub mod day09;
-        pub mod day10;
+/// Solutions for `year` and `day` if given, only of the `variant` if given, skipping days
+/// without it, or else only of the default variant. The default variant is the one registered
+/// without a name, or else the first by name. There's one solution per day unless a variant
+/// was registered more than once.
+pub fn selected_solutions(
+    year: Option<u32>,
+    day: Option<u32>,
+    variant: Option<&str>,
+) -> Vec<RegisteredSolution> {
+    let solutions = filtered_solutions(year, day);
+    match variant {
+        Some(variant) => solutions
+            .into_iter()
+            .filter(|s| s.variant == Some(variant))
+            .collect(),
+        None => solutions
+            .into_iter()
+            .chunk_by(|s| (s.year, s.day))
+            .into_iter()
+            .flat_map(|(_, variants)| {
+                let variants: Vec<_> = variants.collect();
+                let default = variants[0].variant;
+                variants.into_iter().filter(move |s| s.variant == default)
+            })
+            .collect(),
     }
 }
 
-make_solutions!(y2015
-    day01, day02, day03, day04, day05,
-    day06, day07, day08, day09, day10);
-/*[[[end]]]*/
+/// Runs a day from its `parse`, `part1` and `part2`, timing each phase and catching panics.
+#[doc(hidden)]
+pub fn run_day<'a, P, A: PartAnswer, B: PartAnswer>(
+    input: &'a str,
+    parse: impl FnOnce(&'a str) -> P,
+    part1: impl FnOnce(&P) -> A,
+    part2: impl FnOnce(&P) -> B,
+) -> (PartResult, PartResult, Timings) {
+    let instant = Instant::now();
+    let parsed_input = catch_panic(|| parse(input));
+    let parse_elapsed = instant.elapsed();
+
+    let instant = Instant::now();
+    let part1 = match &parsed_input {
+        Ok(parsed_input) => catch_panic(|| part1(parsed_input).into_answer()),
+        Err(panic) => Err(panic.clone()),
+    };
+    let part1_elapsed = instant.elapsed();
+
+    let instant = Instant::now();
+    let part2 = match &parsed_input {
+        Ok(parsed_input) => catch_panic(|| part2(parsed_input).into_answer()),
+        Err(panic) => Err(panic.clone()),
+    };
+    let part2_elapsed = instant.elapsed();
+
+    (
+        part1,
+        part2,
+        Timings {
+            parse: parse_elapsed,
+            part1: part1_elapsed,
+            part2: part2_elapsed,
+        },
+    )
+}
+
+/// Runs a part registered with [`solution`] on a function, returning its answer and how long
+/// parsing and solving took.
+#[doc(hidden)]
+pub fn run_part<'a, P, A: PartAnswer>(
+    input: &'a str,
+    parse: impl FnOnce(&'a str) -> P,
+    part: impl FnOnce(&P) -> A,
+) -> (PartResult, Duration, Duration) {
+    let instant = Instant::now();
+    let parsed_input = catch_panic(|| parse(input));
+    let parse_elapsed = instant.elapsed();
+
+    let instant = Instant::now();
+    let answer = match &parsed_input {
+        Ok(parsed_input) => catch_panic(|| part(parsed_input).into_answer()),
+        Err(panic) => Err(panic.clone()),
+    };
+
+    (answer, parse_elapsed, instant.elapsed())
+}
+
+mod util {
+    pub mod bits;
+    pub mod integer;
+    pub mod ocr;
+    pub mod parse;
+}
+
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
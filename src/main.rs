@@ -1,7 +1,9 @@
 use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
 
 use clap::{Parser, Subcommand};
-use yaaocr::{aoc_proxy, runner};
+use yaaocr::{aoc_proxy, runner, scaffold, watch};
 
 /// Yet Another Advent of Code Runner
 #[derive(Debug, Parser)]
@@ -27,13 +29,139 @@ enum Command {
         #[arg(short, long, required = false, requires = "year", requires = "day")]
         input_path_override: Option<PathBuf>,
 
+        /// Run on an example from the puzzle instead of the input, verifying against its answers
+        #[arg(
+            short,
+            long,
+            required = false,
+            requires = "year",
+            requires = "day",
+            conflicts_with = "input_path_override"
+        )]
+        example: Option<u32>,
+
+        /// Run this variant of each day instead of the default one
+        #[arg(long, required = false)]
+        variant: Option<String>,
+
         /// Verify solutions
         #[arg(short, long, required = false)]
         verify: bool,
 
-        /// Display totals
+        /// Record answers as the expected answers
+        #[arg(short, long, required = false, conflicts_with = "input_path_override")]
+        record: bool,
+
+        /// Let --record overwrite expected answers that differ
+        #[arg(long, required = false, requires = "record")]
+        force: bool,
+
+        /// Display totals (text format only)
         #[arg(short, long, required = false)]
         totals: bool,
+
+        /// Output format
+        #[arg(short, long, required = false, default_value = "text")]
+        format: runner::OutputFormat,
+
+        /// Give up on a day that runs longer than this (e.g. "10s", "2m")
+        #[arg(long, required = false, value_parser = humantime::parse_duration)]
+        timeout: Option<Duration>,
+
+        /// Number of days to run concurrently, 0 for one per core
+        #[arg(
+            short,
+            long,
+            required = false,
+            default_value_t = 1,
+            conflicts_with = "bench"
+        )]
+        jobs: usize,
+
+        /// Benchmark solutions by timing repeated runs
+        #[arg(short, long, required = false)]
+        bench: bool,
+
+        /// Untimed warm-up runs per solution when benchmarking
+        #[arg(long, required = false, default_value_t = 3, requires = "bench")]
+        warmup: u32,
+
+        /// Timed runs per solution when benchmarking
+        #[arg(
+            long,
+            required = false,
+            requires = "bench",
+            conflicts_with = "bench_time"
+        )]
+        iterations: Option<u32>,
+
+        /// Time budget per solution when benchmarking (e.g. "500ms", "5s")
+        #[arg(
+            long,
+            required = false,
+            default_value = "3s",
+            value_parser = humantime::parse_duration,
+            requires = "bench"
+        )]
+        bench_time: Duration,
+    },
+
+    /// Run every variant of each day and check that they agree
+    Compare {
+        /// Year to compare
+        #[arg(short, long, required = false)]
+        year: Option<u32>,
+
+        /// Day to compare
+        #[arg(short, long, required = false)]
+        day: Option<u32>,
+
+        /// Input file to use instead of the default
+        #[arg(short, long, required = false, requires = "year", requires = "day")]
+        input_path_override: Option<PathBuf>,
+
+        /// Compare on an example from the puzzle instead of the input
+        #[arg(
+            short,
+            long,
+            required = false,
+            requires = "year",
+            requires = "day",
+            conflicts_with = "input_path_override"
+        )]
+        example: Option<u32>,
+
+        /// Give up on a variant that runs longer than this (e.g. "10s", "2m")
+        #[arg(long, required = false, value_parser = humantime::parse_duration)]
+        timeout: Option<Duration>,
+
+        /// Number of variants to run concurrently, 0 for one per core
+        #[arg(short, long, required = false, default_value_t = 1)]
+        jobs: usize,
+    },
+
+    /// Rebuild and rerun a day whenever its solution, input or examples change
+    Watch {
+        /// Year to watch
+        #[arg(short, long)]
+        year: u32,
+
+        /// Day to watch
+        #[arg(short, long)]
+        day: u32,
+    },
+
+    /// Create a solution file for a new day
+    New {
+        /// Year of the new day
+        year: u32,
+
+        /// Day to create
+        day: u32,
+
+        /// Download the input and puzzle too
+        #[arg(short, long, required = false)]
+        download: bool,
     },
 
     /// Download puzzles and inputs
@@ -63,10 +191,40 @@ enum Command {
 
         /// Which part to submit
         what: aoc_proxy::PuzzlePart,
+
+        /// Submit the answer of this variant instead of the default one
+        #[arg(long, required = false, conflicts_with = "answer")]
+        variant: Option<String>,
+
+        /// Submit this answer instead of running the solution
+        #[arg(short, long, required = false)]
+        answer: Option<String>,
+
+        /// If an answer was given too recently, wait and submit again
+        #[arg(short, long, required = false)]
+        wait: bool,
+
+        /// Show what would be submitted without submitting it
+        #[arg(short = 'n', long, required = false)]
+        dry_run: bool,
+
+        /// Submit without asking for confirmation
+        #[arg(long, required = false)]
+        yes: bool,
+    },
+
+    /// Run a single solution and print its result as JSON, used to enforce timeouts
+    #[command(hide = true)]
+    Worker {
+        year: u32,
+        day: u32,
+        input_path: PathBuf,
+        #[arg(long)]
+        variant: Option<String>,
     },
 }
 
-fn main() {
+fn main() -> ExitCode {
     let args = App::parse();
 
     if let Err(err) = match args.command {
@@ -74,17 +232,105 @@ fn main() {
             year,
             day,
             input_path_override,
+            example,
+            variant,
             verify,
+            record,
+            force,
             totals,
-        } => runner::run(year, day, input_path_override, verify, totals),
+            format,
+            jobs,
+            timeout,
+            bench,
+            warmup,
+            iterations,
+            bench_time,
+        } => {
+            let bench = bench.then_some(runner::BenchOptions {
+                warmup,
+                iterations,
+                time_budget: bench_time,
+            });
+            runner::run(
+                year,
+                day,
+                &runner::RunOptions {
+                    input_path_override,
+                    verify,
+                    totals,
+                    bench,
+                    format,
+                    jobs,
+                    timeout,
+                    record,
+                    force,
+                    example,
+                    variant,
+                    worker_command: None,
+                },
+            )
+        }
+        Command::Compare {
+            year,
+            day,
+            input_path_override,
+            example,
+            timeout,
+            jobs,
+        } => runner::compare(
+            year,
+            day,
+            &runner::RunOptions {
+                input_path_override,
+                example,
+                timeout,
+                jobs,
+                ..Default::default()
+            },
+        ),
+        Command::Watch { year, day } => watch::watch(year, day),
+        Command::New {
+            year,
+            day,
+            download,
+        } => scaffold::new_day(year, day, download),
         Command::Download {
             year,
             day,
             what,
             force,
         } => aoc_proxy::download(year, day, what, force),
-        Command::Submit { year, day, what } => aoc_proxy::submit(year, day, what),
+        Command::Submit {
+            year,
+            day,
+            what,
+            variant,
+            answer,
+            wait,
+            dry_run,
+            yes,
+        } => aoc_proxy::submit(
+            year,
+            day,
+            what,
+            &aoc_proxy::SubmitOptions {
+                variant,
+                answer,
+                wait,
+                dry_run,
+                confirm: !yes,
+            },
+        ),
+        Command::Worker {
+            year,
+            day,
+            input_path,
+            variant,
+        } => runner::run_worker(year, day, variant, input_path),
     } {
         eprintln!("Error: {err}");
+        return ExitCode::from(err.exit_code());
     }
+
+    ExitCode::SUCCESS
 }
@@ -1,100 +1,1091 @@
-use std::error::Error;
-use std::fs;
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
 use std::time::{Duration, Instant};
+use std::{env, fmt, fs, io, thread};
 
-use crate::{Solution, expected_path, filtered_solutions, input_path};
+use clap::ValueEnum;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use wait_timeout::ChildExt;
 
-pub fn run(
-    year: Option<u32>,
-    day: Option<u32>,
-    input_path_override: Option<PathBuf>,
-    verify: bool,
-    totals: bool,
-) -> Result<(), Box<dyn Error>> {
-    let mut total_elapsed = Duration::ZERO;
+use crate::Error;
+use crate::config::Config;
+use crate::expected::{Change, Expected};
 
-    for Solution { year, day, wrapper } in filtered_solutions(year, day) {
-        let input_path = input_path_override.clone().unwrap_or(input_path(year, day));
+use crate::{
+    Answer, Panic, PartResult, RegisteredSolution, SOLUTION_STACK_SIZE, Timings,
+    example_expected_path, example_path, expected_path, filtered_solutions, input_path,
+    selected_solutions,
+};
 
-        if !input_path.exists() {
-            eprintln!("{year} Day {day:02}");
-            eprintln!("  Missing input!");
-            eprintln!("  Place input file at '{}'", input_path.display());
+/// How to repeat each solution when benchmarking.
+#[derive(Debug, Clone, Copy)]
+pub struct BenchOptions {
+    /// Untimed runs before sampling starts
+    pub warmup: u32,
+    /// Exact number of timed runs, takes precedence over `time_budget`
+    pub iterations: Option<u32>,
+    /// Keep sampling until this much time has been spent on timed runs
+    pub time_budget: Duration,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Stats {
+    runs: usize,
+    min: Duration,
+    median: Duration,
+    mean: Duration,
+    stddev: Duration,
+}
+
+impl Stats {
+    fn from_samples(samples: &mut [Duration]) -> Self {
+        samples.sort_unstable();
+
+        let runs = samples.len();
+        let median = if runs.is_multiple_of(2) {
+            (samples[runs / 2 - 1] + samples[runs / 2]) / 2
+        } else {
+            samples[runs / 2]
+        };
+
+        let secs: Vec<f64> = samples.iter().map(Duration::as_secs_f64).collect();
+        let mean = secs.iter().sum::<f64>() / runs as f64;
+        let variance = if runs > 1 {
+            secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (runs - 1) as f64
+        } else {
+            0.0
+        };
+
+        Self {
+            runs,
+            min: samples[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// Format of the report written to stdout.
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Verification {
+    Correct,
+    Incorrect,
+    Unverified,
+    Panicked,
+    NotImplemented,
+}
+
+struct PartReport {
+    answer: PartResult,
+    expected: Option<Answer>,
+}
+
+impl PartReport {
+    fn verification(&self) -> Verification {
+        match (&self.answer, &self.expected) {
+            (Err(_), _) => Verification::Panicked,
+            (Ok(None), _) => Verification::NotImplemented,
+            (Ok(Some(answer)), Some(expected)) if answer.matches(expected) => Verification::Correct,
+            (Ok(Some(_)), Some(_)) => Verification::Incorrect,
+            (Ok(Some(_)), None) => Verification::Unverified,
+        }
+    }
+}
+
+struct DayRun {
+    part1: PartReport,
+    part2: PartReport,
+    timings: Timings,
+    bench: Option<(Stats, Timings)>,
+}
+
+enum DayError {
+    MissingInput(PathBuf),
+    ReadFailed(PathBuf, io::Error),
+    TimedOut(Duration),
+    WorkerFailed(String),
+}
+
+impl fmt::Display for DayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DayError::MissingInput(path) => write!(f, "Missing input '{}'", path.display()),
+            DayError::ReadFailed(path, err) => {
+                write!(f, "Failed to read '{}': {err}", path.display())
+            }
+            DayError::TimedOut(timeout) => {
+                write!(
+                    f,
+                    "Timed out after {}",
+                    humantime::format_duration(*timeout)
+                )
+            }
+            DayError::WorkerFailed(err) => write!(f, "Worker process failed: {err}"),
+        }
+    }
+}
+
+struct DayReport {
+    year: u32,
+    day: u32,
+    variant: Option<&'static str>,
+    example: Option<u32>,
+    expected_path: PathBuf,
+    outcome: Result<DayRun, DayError>,
+}
+
+/// Number of days by their worst outcome.
+#[derive(Default)]
+struct Summary {
+    correct: usize,
+    incorrect: usize,
+    unverified: usize,
+    not_implemented: usize,
+    panicked: usize,
+    timed_out: usize,
+    missing_inputs: Vec<PathBuf>,
+    not_run: usize,
+}
+
+impl Summary {
+    fn add(&mut self, report: &DayReport) {
+        let run = match &report.outcome {
+            Ok(run) => run,
+            Err(DayError::TimedOut(_)) => {
+                self.timed_out += 1;
+                return;
+            }
+            Err(DayError::MissingInput(input_path)) => {
+                self.missing_inputs.push(input_path.clone());
+                return;
+            }
+            Err(_) => {
+                self.not_run += 1;
+                return;
+            }
+        };
+
+        // A day is judged by its implemented parts, so one without a part 2 can still be correct
+        let verifications: Vec<_> = [run.part1.verification(), run.part2.verification()]
+            .into_iter()
+            .filter(|verification| *verification != Verification::NotImplemented)
+            .collect();
+        let count = if verifications.is_empty() {
+            &mut self.not_implemented
+        } else if verifications.contains(&Verification::Panicked) {
+            &mut self.panicked
+        } else if verifications.contains(&Verification::Incorrect) {
+            &mut self.incorrect
+        } else if verifications.contains(&Verification::Unverified) {
+            &mut self.unverified
+        } else {
+            &mut self.correct
+        };
+        *count += 1;
+    }
+
+    /// Fails if any day went wrong, with wrong answers taking precedence over missing inputs.
+    fn into_result(self) -> Result<(), Error> {
+        if self.incorrect + self.panicked + self.timed_out > 0 {
+            Err(Error::Verification {
+                incorrect: self.incorrect,
+                panicked: self.panicked,
+                timed_out: self.timed_out,
+            })
+        } else if !self.missing_inputs.is_empty() {
+            Err(Error::MissingInput(self.missing_inputs))
         } else {
-            match fs::read_to_string(&input_path) {
-                Ok(input) => {
-                    println!("{year} Day {day:02}");
+            Ok(())
+        }
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let counts = [
+            (self.correct, "correct"),
+            (self.incorrect, "incorrect"),
+            (self.unverified, "unverified"),
+            (self.not_implemented, "not implemented"),
+            (self.panicked, "panicked"),
+            (self.timed_out, "timed out"),
+            (self.missing_inputs.len(), "missing input"),
+            (self.not_run, "not run"),
+        ];
+        let total: usize = counts.iter().map(|(n, _)| n).sum();
+
+        write!(f, "{total} days")?;
+        for (i, (n, label)) in counts.iter().filter(|(n, _)| *n > 0).enumerate() {
+            write!(f, "{} {n} {label}", if i == 0 { ":" } else { "," })?;
+        }
+        Ok(())
+    }
+}
+
+/// One row of a JSON or CSV report. Times are in seconds, and are medians when benchmarking.
+#[derive(Serialize)]
+struct Record<'a> {
+    year: u32,
+    day: u32,
+    variant: Option<&'a str>,
+    part1: Option<String>,
+    part1_expected: Option<String>,
+    part1_status: Option<Verification>,
+    part1_panic: Option<String>,
+    part2: Option<String>,
+    part2_expected: Option<String>,
+    part2_status: Option<Verification>,
+    part2_panic: Option<String>,
+    elapsed: Option<f64>,
+    parse_elapsed: Option<f64>,
+    part1_elapsed: Option<f64>,
+    part2_elapsed: Option<f64>,
+    runs: Option<usize>,
+    min_elapsed: Option<f64>,
+    mean_elapsed: Option<f64>,
+    stddev_elapsed: Option<f64>,
+    error: Option<String>,
+}
+
+impl<'a> From<&'a DayReport> for Record<'a> {
+    fn from(report: &'a DayReport) -> Self {
+        let mut record = Record {
+            year: report.year,
+            day: report.day,
+            variant: report.variant,
+            part1: None,
+            part1_expected: None,
+            part1_status: None,
+            part1_panic: None,
+            part2: None,
+            part2_expected: None,
+            part2_status: None,
+            part2_panic: None,
+            elapsed: None,
+            parse_elapsed: None,
+            part1_elapsed: None,
+            part2_elapsed: None,
+            runs: None,
+            min_elapsed: None,
+            mean_elapsed: None,
+            stddev_elapsed: None,
+            error: None,
+        };
+
+        match &report.outcome {
+            Ok(run) => {
+                record.part1 = run
+                    .part1
+                    .answer
+                    .as_ref()
+                    .ok()
+                    .and_then(Option::as_ref)
+                    .map(Answer::to_string);
+                record.part1_expected = run.part1.expected.as_ref().map(Answer::to_string);
+                record.part1_status = Some(run.part1.verification());
+                record.part1_panic = run.part1.answer.as_ref().err().map(Panic::to_string);
+                record.part2 = run
+                    .part2
+                    .answer
+                    .as_ref()
+                    .ok()
+                    .and_then(Option::as_ref)
+                    .map(Answer::to_string);
+                record.part2_expected = run.part2.expected.as_ref().map(Answer::to_string);
+                record.part2_status = Some(run.part2.verification());
+                record.part2_panic = run.part2.answer.as_ref().err().map(Panic::to_string);
+
+                let (elapsed, timings) = match &run.bench {
+                    Some((stats, medians)) => {
+                        record.runs = Some(stats.runs);
+                        record.min_elapsed = Some(stats.min.as_secs_f64());
+                        record.mean_elapsed = Some(stats.mean.as_secs_f64());
+                        record.stddev_elapsed = Some(stats.stddev.as_secs_f64());
+                        (stats.median, medians)
+                    }
+                    None => (run.timings.total(), &run.timings),
+                };
+                record.elapsed = Some(elapsed.as_secs_f64());
+                record.parse_elapsed = Some(timings.parse.as_secs_f64());
+                record.part1_elapsed = Some(timings.part1.as_secs_f64());
+                record.part2_elapsed = Some(timings.part2.as_secs_f64());
+            }
+            Err(err) => record.error = Some(err.to_string()),
+        }
+
+        record
+    }
+}
+
+/// Options for [`run`] and [`compare`].
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    /// Input file to use instead of the default
+    pub input_path_override: Option<PathBuf>,
+    /// Compare answers against the expected answer files
+    pub verify: bool,
+    /// Display totals (text format only)
+    pub totals: bool,
+    /// Benchmark each solution instead of timing a single run
+    pub bench: Option<BenchOptions>,
+    pub format: OutputFormat,
+    /// Number of days to run concurrently, 0 for one per available core
+    pub jobs: usize,
+    /// Give up on a day after this long, unless the config overrides it for that day. Days with
+    /// a timeout run in a worker process, see `worker_command`.
+    pub timeout: Option<Duration>,
+    /// Executable to run days with a timeout in, as
+    /// `<worker_command> worker <year> <day> <input> [--variant <variant>]`, which has to print
+    /// the result as the `yaaocr` CLI's hidden `worker` command does (see [`run_worker`]).
+    /// Defaults to the current executable, which is only right when that's the CLI.
+    pub worker_command: Option<PathBuf>,
+    /// Write the answers to the expected answer files
+    pub record: bool,
+    /// Let `record` replace expected answers that differ
+    pub force: bool,
+    /// Run on this example from the puzzle instead of the input, verifying against its answers
+    pub example: Option<u32>,
+    /// Run this variant of each day instead of the default one
+    pub variant: Option<String>,
+}
+
+pub fn run(year: Option<u32>, day: Option<u32>, options: &RunOptions) -> Result<(), Error> {
+    let solutions = selected_solutions(year, day, options.variant.as_deref());
+    if solutions.is_empty() {
+        match (year, day, &options.variant) {
+            (Some(year), Some(day), Some(variant)) => {
+                return Err(Error::NoVariant {
+                    year,
+                    day,
+                    variant: variant.clone(),
+                });
+            }
+            (Some(year), Some(day), None) => return Err(Error::NoSolution { year, day }),
+            (year, day, Some(variant)) => {
+                return Err(Error::NoMatchingVariant {
+                    year,
+                    day,
+                    variant: variant.clone(),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    let config = Config::load()?;
+    let instant = Instant::now();
+    let mut summary = Summary::default();
+
+    match options.format {
+        OutputFormat::Text => {
+            let mut total_elapsed = Timings::default();
+            let mut total_median = Duration::ZERO;
+            let mut total_mean = Duration::ZERO;
 
-                    let instant = Instant::now();
-                    let (part1, part2) = wrapper(&input);
-                    let elapsed = instant.elapsed();
-                    total_elapsed += elapsed;
+            for_each_report(&solutions, options, &config, |report| {
+                print_text(&report);
+                if let Some(message) = record_message(&report, options) {
+                    println!("{message}");
+                }
+                println!();
+                summary.add(&report);
 
-                    let (part1_expected, part2_expected) = try_read_expected(year, day, verify);
+                if let Ok(run) = &report.outcome {
+                    match &run.bench {
+                        Some((stats, medians)) => {
+                            total_elapsed += *medians;
+                            total_median += stats.median;
+                            total_mean += stats.mean;
+                        }
+                        None => total_elapsed += run.timings,
+                    }
+                }
+            });
+            let wall_clock = instant.elapsed();
 
+            if options.totals {
+                if options.bench.is_some() {
                     println!(
-                        "  Part 1: {}{}",
-                        part1.clone(),
-                        verification_str(part1, part1_expected)
+                        "Total median time: {:.3?}, total mean time: {:.3?}",
+                        total_median, total_mean
                     );
                     println!(
-                        "  Part 2: {}{}",
-                        part2.clone(),
-                        verification_str(part2, part2_expected)
+                        "  Median parse {:.3?}, part 1 {:.3?}, part 2 {:.3?}",
+                        total_elapsed.parse, total_elapsed.part1, total_elapsed.part2
                     );
-                    println!("Elapsed: {:.03}s", elapsed.as_nanos() as f64 / 1e9);
-                }
-                Err(err) => {
-                    eprintln!("{year} Day {day:02}");
-                    eprintln!("  Failed to read '{}': {err}", input_path.display());
+                } else {
+                    println!("Total elapsed time: {}", timings_str(&total_elapsed));
+                    if options.jobs != 1 {
+                        println!(
+                            "Wall-clock time: {:.03}s ({:.2}x speedup)",
+                            wall_clock.as_secs_f64(),
+                            total_elapsed.total().as_secs_f64() / wall_clock.as_secs_f64()
+                        );
+                    }
                 }
+                println!("{summary}");
+                println!();
             }
         }
+        OutputFormat::Json => {
+            let mut reports = vec![];
+            for_each_report(&solutions, options, &config, |report| {
+                if let Some(message) = record_message(&report, options) {
+                    eprintln!("{message}");
+                }
+                summary.add(&report);
+                reports.push(report);
+            });
 
-        println!();
+            let records: Vec<Record> = reports.iter().map(Record::from).collect();
+            serde_json::to_writer_pretty(io::stdout().lock(), &records)
+                .map_err(|err| Error::Output(err.to_string()))?;
+            println!();
+        }
+        OutputFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(io::stdout().lock());
+            let mut result = Ok(());
+            for_each_report(&solutions, options, &config, |report| {
+                if let Some(message) = record_message(&report, options) {
+                    eprintln!("{message}");
+                }
+                summary.add(&report);
+                if result.is_ok() {
+                    result = writer.serialize(Record::from(&report));
+                }
+            });
+            result?;
+            writer.flush()?;
+        }
+    }
+
+    summary.into_result()
+}
+
+/// Runs every variant of each day on the same input, and checks that they give the same
+/// answers. Days with a single variant are skipped.
+pub fn compare(year: Option<u32>, day: Option<u32>, options: &RunOptions) -> Result<(), Error> {
+    let solutions: Vec<_> = filtered_solutions(year, day)
+        .into_iter()
+        .chunk_by(|s| (s.year, s.day))
+        .into_iter()
+        .map(|(_, variants)| variants.collect::<Vec<_>>())
+        .filter(|variants| variants.len() > 1)
+        .flatten()
+        .collect();
+    if solutions.is_empty() {
+        println!("No days with more than one variant to compare");
+        return Ok(());
     }
 
-    if totals {
-        println!(
-            "Total elapsed time: {:.03}s",
-            total_elapsed.as_nanos() as f64 / 1e9
-        );
+    let config = Config::load()?;
+    let mut disagreeing = 0;
+    let mut failed = 0;
+
+    let mut variants: Vec<DayReport> = vec![];
+    let mut finish_day = |variants: &mut Vec<DayReport>| {
+        if variants.is_empty() {
+            return;
+        }
+        match print_comparison(variants) {
+            Comparison::Agree => {}
+            Comparison::Disagree => disagreeing += 1,
+            Comparison::Failed => failed += 1,
+        }
         println!();
+        variants.clear();
+    };
+
+    for_each_report(&solutions, options, &config, |report| {
+        if variants
+            .first()
+            .is_some_and(|first| (first.year, first.day) != (report.year, report.day))
+        {
+            finish_day(&mut variants);
+        }
+        variants.push(report);
+    });
+    finish_day(&mut variants);
+
+    if disagreeing + failed > 0 {
+        Err(Error::Comparison {
+            disagreeing,
+            failed,
+        })
+    } else {
+        Ok(())
     }
+}
 
-    Ok(())
+enum Comparison {
+    Agree,
+    Disagree,
+    Failed,
 }
 
-fn try_read_expected(year: u32, day: u32, verify: bool) -> (Option<String>, Option<String>) {
-    let expected_path = expected_path(year, day);
-    if verify && expected_path.exists() {
-        match fs::read_to_string(&expected_path) {
-            Ok(content) => {
-                let mut lines = content.lines();
-                (
-                    lines.next().map(|s| s.to_owned()),
-                    lines.next().map(|s| s.to_owned()),
-                )
+/// Prints the answers and time of each variant of a day side by side, and whether they agree.
+fn print_comparison(variants: &[DayReport]) -> Comparison {
+    let first = &variants[0];
+    let header = match first.example {
+        Some(example) => format!("{} Day {:02} (example {example})", first.year, first.day),
+        None => format!("{} Day {:02}", first.year, first.day),
+    };
+    println!("{header}");
+
+    let cell = |part: &PartReport| match &part.answer {
+        Ok(Some(Answer::Grid(rows))) if rows.len() > 1 => format!(
+            "{}x{} picture",
+            rows.iter()
+                .map(|row| row.chars().count())
+                .max()
+                .unwrap_or(0),
+            rows.len()
+        ),
+        Ok(Some(answer)) => answer.to_string(),
+        Ok(None) => "not implemented".to_owned(),
+        Err(_) => "⚠ panicked".to_owned(),
+    };
+    let rows: Vec<[String; 4]> = variants
+        .iter()
+        .map(|report| {
+            let name = report.variant.unwrap_or("default").to_owned();
+            match &report.outcome {
+                Ok(run) => [
+                    name,
+                    cell(&run.part1),
+                    cell(&run.part2),
+                    format!("{:.3?}", run.timings.total()),
+                ],
+                Err(err) => [name, format!("⚠ {err}"), String::new(), String::new()],
             }
-            Err(err) => {
-                eprintln!("  Failed to read '{}': {err}", expected_path.display());
-                (None, None)
+        })
+        .collect();
+
+    let header_row = ["Variant", "Part 1", "Part 2", "Elapsed"].map(str::to_owned);
+    let widths: Vec<usize> = (0..4)
+        .map(|i| {
+            rows.iter()
+                .chain([&header_row])
+                .map(|row| row[i].chars().count())
+                .max()
+                .unwrap()
+        })
+        .collect();
+    for row in [&header_row].into_iter().chain(&rows) {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:width$}"))
+            .join("  ");
+        println!("  {}", line.trim_end());
+    }
+
+    let mut failed = false;
+    let mut answers: [Vec<&Answer>; 2] = [vec![], vec![]];
+    for report in variants {
+        let Ok(run) = &report.outcome else {
+            failed = true;
+            continue;
+        };
+        for (i, part) in [&run.part1, &run.part2].into_iter().enumerate() {
+            match &part.answer {
+                Ok(Some(answer)) => answers[i].push(answer),
+                Ok(None) => {}
+                Err(panic) => {
+                    failed = true;
+                    let name = report.variant.unwrap_or("default");
+                    println!("  ⚠ {name} part {}: {panic}", i + 1);
+                }
             }
         }
+    }
+
+    let disagreeing: Vec<_> = answers
+        .iter()
+        .enumerate()
+        .filter(|(_, answers)| !answers.iter().all(|answer| answer.matches(answers[0])))
+        .map(|(i, _)| format!("part {}", i + 1))
+        .collect();
+    if !disagreeing.is_empty() {
+        println!("  ✗ Variants disagree on {}", disagreeing.join(" and "));
+        Comparison::Disagree
+    } else if failed {
+        Comparison::Failed
     } else {
-        (None, None)
+        println!("  ✓ All {} variants agree", variants.len());
+        Comparison::Agree
     }
 }
 
-fn verification_str(actual: String, expected: Option<String>) -> String {
-    if let Some(expected) = expected {
-        if actual == expected {
-            " ✓".to_owned()
-        } else {
-            format!(" ✗ ({})", expected)
+/// Runs every solution, passing each report to `sink` in year/day order as soon as all the
+/// reports before it are done.
+fn for_each_report(
+    solutions: &[RegisteredSolution],
+    options: &RunOptions,
+    config: &Config,
+    mut sink: impl FnMut(DayReport),
+) {
+    let run_one = |solution: &RegisteredSolution| {
+        let (year, day) = (solution.year, solution.day);
+        let (input_path, expected_path) = match options.example {
+            Some(example) => (
+                example_path(year, day, example),
+                example_expected_path(year, day, example),
+            ),
+            None => (
+                options
+                    .input_path_override
+                    .clone()
+                    .unwrap_or(input_path(year, day)),
+                expected_path(year, day),
+            ),
+        };
+        let timeout = config.timeout(year, day, options.timeout);
+        let verify = options.verify || options.example.is_some();
+
+        DayReport {
+            example: options.example,
+            ..run_day(
+                solution,
+                input_path,
+                expected_path,
+                verify,
+                options.bench,
+                timeout,
+                options.worker_command.as_deref(),
+            )
         }
+    };
+
+    let jobs = match options.jobs {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        jobs => jobs,
+    };
+    if jobs <= 1 || solutions.len() <= 1 {
+        solutions.iter().map(run_one).for_each(sink);
+        return;
+    }
+
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(solutions.len()) {
+            let (next, run_one, tx) = (&next, &run_one, tx.clone());
+            let worker = thread::Builder::new().stack_size(SOLUTION_STACK_SIZE);
+            worker
+                .spawn_scoped(scope, move || {
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(solution) = solutions.get(i) else {
+                            break;
+                        };
+                        if tx.send((i, run_one(solution))).is_err() {
+                            break;
+                        }
+                    }
+                })
+                .expect("Failed to spawn worker thread");
+        }
+        drop(tx);
+
+        let mut pending = BTreeMap::new();
+        let mut next_out = 0;
+        for (i, report) in rx {
+            pending.insert(i, report);
+            while let Some(report) = pending.remove(&next_out) {
+                sink(report);
+                next_out += 1;
+            }
+        }
+    });
+}
+
+fn run_day(
+    solution @ &RegisteredSolution {
+        year, day, variant, ..
+    }: &RegisteredSolution,
+    input_path: PathBuf,
+    expected_path: PathBuf,
+    verify: bool,
+    bench: Option<BenchOptions>,
+    timeout: Option<Duration>,
+    worker_command: Option<&Path>,
+) -> DayReport {
+    let outcome = if !input_path.exists() {
+        Err(DayError::MissingInput(input_path))
     } else {
-        String::new()
+        match fs::read_to_string(&input_path) {
+            Ok(input) => (|| {
+                let (part1, part2, timings) = match timeout {
+                    Some(timeout) => run_isolated(solution, &input_path, timeout, worker_command)?,
+                    None => solution.run(&input),
+                };
+                let expected = try_read_expected(&expected_path, verify);
+
+                // A solution that panicked once will keep panicking, there's nothing to time
+                let bench = bench
+                    .filter(|_| part1.is_ok() && part2.is_ok())
+                    .map(|bench| bench_solution(solution, &input, bench));
+
+                Ok(DayRun {
+                    part1: PartReport {
+                        answer: part1,
+                        expected: expected.part1,
+                    },
+                    part2: PartReport {
+                        answer: part2,
+                        expected: expected.part2,
+                    },
+                    timings,
+                    bench,
+                })
+            })(),
+            Err(err) => Err(DayError::ReadFailed(input_path, err)),
+        }
+    };
+
+    DayReport {
+        year,
+        day,
+        variant,
+        example: None,
+        expected_path,
+        outcome,
+    }
+}
+
+fn print_text(report: &DayReport) {
+    let header = header(report);
+
+    match &report.outcome {
+        Ok(run) => {
+            println!("{header}");
+            println!("{}", part_str(1, &run.part1));
+            println!("{}", part_str(2, &run.part2));
+
+            if let Some((stats, medians)) = &run.bench {
+                println!(
+                    "Elapsed: min {:.3?}, median {:.3?}, mean {:.3?}, stddev {:.3?} ({} runs)",
+                    stats.min, stats.median, stats.mean, stats.stddev, stats.runs
+                );
+                println!(
+                    "  Median parse {:.3?}, part 1 {:.3?}, part 2 {:.3?}",
+                    medians.parse, medians.part1, medians.part2
+                );
+            } else {
+                println!("Elapsed: {}", timings_str(&run.timings));
+            }
+        }
+        Err(DayError::MissingInput(input_path)) => {
+            eprintln!("{header}");
+            eprintln!("  Missing input!");
+            eprintln!("  Place input file at '{}'", input_path.display());
+        }
+        Err(err @ DayError::ReadFailed(..)) => {
+            eprintln!("{header}");
+            eprintln!("  {err}");
+        }
+        Err(err @ (DayError::TimedOut(_) | DayError::WorkerFailed(_))) => {
+            println!("{header}");
+            println!("  ⚠ {err}");
+        }
+    }
+}
+
+/// Result of running a solution in a worker process, written to its stdout as JSON.
+#[derive(Serialize, Deserialize)]
+struct WorkerOutput {
+    part1: PartResult,
+    part2: PartResult,
+    timings: Timings,
+}
+
+/// Entry point of the worker process that [`run`] starts for days with a timeout.
+pub fn run_worker(
+    year: u32,
+    day: u32,
+    variant: Option<String>,
+    input_path: PathBuf,
+) -> Result<(), Error> {
+    let solutions = selected_solutions(Some(year), Some(day), variant.as_deref());
+    let Some(solution) = solutions.into_iter().next() else {
+        return Err(Error::NoSolution { year, day });
+    };
+
+    let input = fs::read_to_string(input_path)?;
+    let (part1, part2, timings) = solution.run(&input);
+
+    let output = WorkerOutput {
+        part1,
+        part2,
+        timings,
+    };
+    println!(
+        "{}",
+        serde_json::to_string(&output).map_err(io::Error::from)?
+    );
+
+    Ok(())
+}
+
+/// Reads everything from `pipe` on another thread.
+fn read_in_background(
+    mut pipe: impl Read + Send + 'static,
+) -> thread::JoinHandle<io::Result<Vec<u8>>> {
+    thread::spawn(move || {
+        let mut output = vec![];
+        pipe.read_to_end(&mut output).map(|_| output)
+    })
+}
+
+/// Runs a solution in a worker process, so that it can be killed if it takes too long.
+fn run_isolated(
+    solution: &RegisteredSolution,
+    input_path: &Path,
+    timeout: Duration,
+    worker_command: Option<&Path>,
+) -> Result<(PartResult, PartResult, Timings), DayError> {
+    let worker_failed = |err: io::Error| DayError::WorkerFailed(err.to_string());
+
+    let program = match worker_command {
+        Some(program) => program.to_owned(),
+        None => env::current_exe().map_err(worker_failed)?,
+    };
+    let mut command = Command::new(program);
+    command
+        .arg("worker")
+        .arg(solution.year.to_string())
+        .arg(solution.day.to_string())
+        .arg(input_path);
+    if let Some(variant) = solution.variant {
+        command.arg("--variant").arg(variant);
+    }
+
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(worker_failed)?;
+
+    // Read the output while waiting, a worker that fills a pipe would block until it's read
+    let stdout = read_in_background(child.stdout.take().unwrap());
+    let stderr = read_in_background(child.stderr.take().unwrap());
+
+    let status = child.wait_timeout(timeout).map_err(worker_failed);
+    if !matches!(status, Ok(Some(_))) {
+        child.kill().ok();
+        child.wait().ok();
+    }
+    let (stdout, stderr) = (stdout.join().unwrap(), stderr.join().unwrap());
+    let Some(status) = status? else {
+        return Err(DayError::TimedOut(timeout));
+    };
+    let (stdout, stderr) = (
+        stdout.map_err(worker_failed)?,
+        stderr.map_err(worker_failed)?,
+    );
+
+    if !status.success() {
+        let stderr = String::from_utf8_lossy(&stderr);
+        return Err(DayError::WorkerFailed(format!(
+            "{status}\n{}",
+            stderr.trim_end()
+        )));
+    }
+
+    let stdout = String::from_utf8_lossy(&stdout);
+    let output: WorkerOutput = stdout
+        .lines()
+        .last()
+        .and_then(|line| serde_json::from_str(line).ok())
+        .ok_or_else(|| DayError::WorkerFailed(format!("Unexpected output: {stdout}")))?;
+
+    Ok((output.part1, output.part2, output.timings))
+}
+
+/// Returns statistics over the total time of each run, and the median time of each phase.
+fn bench_solution(
+    solution: &RegisteredSolution,
+    input: &str,
+    bench: BenchOptions,
+) -> (Stats, Timings) {
+    for _ in 0..bench.warmup {
+        let _ = solution.run(input);
+    }
+
+    let mut samples: Vec<Timings> = vec![];
+    let start = Instant::now();
+    loop {
+        let (_, _, timings) = solution.run(input);
+        samples.push(timings);
+
+        let done = match bench.iterations {
+            Some(iterations) => samples.len() >= iterations as usize,
+            None => start.elapsed() >= bench.time_budget,
+        };
+        if done {
+            break;
+        }
+    }
+
+    let phase_stats = |phase: fn(&Timings) -> Duration| {
+        Stats::from_samples(&mut samples.iter().map(phase).collect::<Vec<_>>())
+    };
+
+    (
+        phase_stats(Timings::total),
+        Timings {
+            parse: phase_stats(|t| t.parse).median,
+            part1: phase_stats(|t| t.part1).median,
+            part2: phase_stats(|t| t.part2).median,
+        },
+    )
+}
+
+/// Like "2015 Day 09 (variant dp, example 1)".
+fn header(report: &DayReport) -> String {
+    let mut details = vec![];
+    if let Some(variant) = report.variant {
+        details.push(format!("variant {variant}"));
+    }
+    if let Some(example) = report.example {
+        details.push(format!("example {example}"));
+    }
+
+    let mut header = format!("{} Day {:02}", report.year, report.day);
+    if !details.is_empty() {
+        header += &format!(" ({})", details.join(", "));
+    }
+    header
+}
+
+fn timings_str(timings: &Timings) -> String {
+    format!(
+        "{:.03}s (parse {:.03}s, part 1 {:.03}s, part 2 {:.03}s)",
+        timings.total().as_secs_f64(),
+        timings.parse.as_secs_f64(),
+        timings.part1.as_secs_f64(),
+        timings.part2.as_secs_f64()
+    )
+}
+
+fn try_read_expected(expected_path: &Path, verify: bool) -> Expected {
+    if verify {
+        Expected::read(expected_path).unwrap_or_else(|err| {
+            eprintln!("  Failed to read '{}': {err}", expected_path.display());
+            Expected::default()
+        })
+    } else {
+        Expected::default()
+    }
+}
+
+/// Records the answers of a day if `--record` was given, returning what to tell the user.
+fn record_message(report: &DayReport, options: &RunOptions) -> Option<String> {
+    if !options.record {
+        return None;
+    }
+
+    record_expected(report, options.force)
+        .unwrap_or_else(|err| Some(format!("Failed to record expected answers: {err}")))
+}
+
+/// Writes the answers of a day to its expected answer file, unless that would replace a
+/// different answer and `force` isn't set. Returns what changed, or nothing if the day didn't
+/// run.
+fn record_expected(report: &DayReport, force: bool) -> Result<Option<String>, Error> {
+    let Ok(run) = &report.outcome else {
+        return Ok(None);
+    };
+
+    let expected_path = &report.expected_path;
+    let expected = Expected::read(expected_path)?;
+
+    let answers = [
+        run.part1.answer.as_ref().ok().and_then(Option::as_ref),
+        run.part2.answer.as_ref().ok().and_then(Option::as_ref),
+    ];
+    let changes = expected.changes(answers[0], answers[1]);
+    if changes.iter().all(|change| *change == Change::Unchanged) {
+        return Ok(Some("Expected answers unchanged".to_owned()));
+    }
+
+    let replaces = changes
+        .iter()
+        .any(|change| matches!(change, Change::Replaced { .. }));
+    let mut message = if replaces && !force {
+        format!(
+            "Not recording '{}', answers differ, use --force to overwrite",
+            expected_path.display()
+        )
+    } else {
+        format!("Recorded '{}'", expected_path.display())
+    };
+
+    for (i, change) in changes.iter().enumerate() {
+        if *change != Change::Unchanged {
+            message += &format!("\n  Part {}: {change}", i + 1);
+        }
+    }
+
+    if !replaces || force {
+        expected
+            .with_answers(answers[0], answers[1])
+            .write(expected_path)?;
+    }
+
+    Ok(Some(message))
+}
+
+/// The line for a part of the text report. Pictures and the expected pictures they're checked
+/// against go on the lines below it.
+fn part_str(part_number: u8, part: &PartReport) -> String {
+    let label = format!("  Part {part_number}:");
+    let Ok(Some(answer)) = &part.answer else {
+        return match &part.answer {
+            Ok(_) => format!("{label} not implemented"),
+            Err(panic) => format!("{label} ⚠ {panic}"),
+        };
+    };
+
+    let indented = |answer: &Answer| {
+        answer
+            .to_string()
+            .lines()
+            .map(|row| format!("\n    {row}"))
+            .collect::<String>()
+    };
+    let multiline =
+        answer.is_multiline() || part.expected.as_ref().is_some_and(Answer::is_multiline);
+    match &part.expected {
+        Some(expected) if answer.matches(expected) && multiline => {
+            format!("{label} ✓{}", indented(answer))
+        }
+        Some(expected) if answer.matches(expected) => format!("{label} {answer} ✓"),
+        Some(expected) if multiline => format!(
+            "{label} ✗{}\n  Expected:{}",
+            indented(answer),
+            indented(expected)
+        ),
+        Some(expected) => format!("{label} {answer} ✗ ({expected})"),
+        None if multiline => format!("{label}{}", indented(answer)),
+        None => format!("{label} {answer}"),
     }
 }
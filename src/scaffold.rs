@@ -0,0 +1,74 @@
+use std::fs;
+use std::io;
+
+use regex::Regex;
+
+use crate::aoc_proxy::{self, DownloadTarget};
+use crate::{Error, check_year_day, puzzle_path, solution_path};
+
+/// Creates a solution file for a day from a template, optionally downloading its input and
+/// puzzle first. The new file registers itself and is picked up on the next build.
+pub fn new_day(year: u32, day: u32, download: bool) -> Result<(), Error> {
+    if !check_year_day(year, day) {
+        return Err(Error::InvalidPuzzle { year, day });
+    }
+
+    let solution_path = solution_path(year, day);
+    if solution_path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("'{}' already exists", solution_path.display()),
+        )
+        .into());
+    }
+
+    if download {
+        aoc_proxy::download(year, day, DownloadTarget::Both, false)?;
+    }
+
+    fs::create_dir_all(solution_path.parent().unwrap())?;
+    fs::write(
+        &solution_path,
+        template(year, day, &puzzle_title(year, day)),
+    )?;
+    println!("Created '{}'", solution_path.display());
+
+    Ok(())
+}
+
+/// The title of a downloaded puzzle, like "Day 7: Some Assembly Required".
+fn puzzle_title(year: u32, day: u32) -> String {
+    fs::read_to_string(puzzle_path(year, day))
+        .ok()
+        .and_then(|puzzle| {
+            Regex::new(r"--- (Day \d+: .+?) ---")
+                .unwrap()
+                .captures(&puzzle)
+                .map(|c| c[1].to_owned())
+        })
+        .unwrap_or_else(|| format!("Day {day}"))
+}
+
+fn template(year: u32, day: u32, title: &str) -> String {
+    format!(
+        r#"/* {title}
+ * https://adventofcode.com/{year}/day/{day}
+ */
+
+use crate::NotImplemented;
+
+#[yaaocr::solution(year = {year}, day = {day})]
+pub fn parse(input: &str) -> &str {{
+    input
+}}
+
+pub fn part1(_input: &str) -> NotImplemented {{
+    NotImplemented
+}}
+
+pub fn part2(_input: &str) -> NotImplemented {{
+    NotImplemented
+}}
+"#
+    )
+}
@@ -2,6 +2,7 @@
  * https://adventofcode.com/2015/day/1
  */
 
+#[yaaocr::solution(year = 2015, day = 1)]
 pub fn parse(input: &str) -> &str {
     input
 }
@@ -5,6 +5,7 @@
 use crate::util::parse::ParseOps;
 use std::cmp::min;
 
+#[yaaocr::solution(year = 2015, day = 2)]
 pub fn parse(input: &str) -> Vec<[u32; 3]> {
     input.iter_unsigned().array_chunks().collect()
 }
@@ -4,6 +4,7 @@
 
 use std::collections::HashSet;
 
+#[yaaocr::solution(year = 2015, day = 3)]
 pub fn parse(input: &str) -> &str {
     input
 }
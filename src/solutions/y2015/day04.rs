@@ -18,6 +18,7 @@ fn compare_exchange_min(slot: &AtomicUsize, v: usize) {
     }
 }
 
+#[yaaocr::solution(year = 2015, day = 4)]
 pub fn parse(input: &str) -> (usize, usize) {
     let input = input.trim().to_owned();
 
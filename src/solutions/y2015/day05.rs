@@ -5,6 +5,7 @@
 use itertools::Itertools;
 use std::collections::HashMap;
 
+#[yaaocr::solution(year = 2015, day = 5)]
 pub fn parse(input: &str) -> Vec<&str> {
     input.lines().collect()
 }
@@ -11,6 +11,7 @@ pub enum Instruction {
     Toggle,
 }
 
+#[yaaocr::solution(year = 2015, day = 6)]
 pub fn parse(input: &str) -> Vec<(Instruction, [usize; 4])> {
     input
         .lines()
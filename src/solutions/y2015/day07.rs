@@ -2,6 +2,7 @@
  * https://adventofcode.com/2015/day/7
  */
 
+use crate::Solution;
 use regex::Regex;
 use std::collections::HashMap;
 
@@ -22,33 +23,6 @@ pub enum Wire<'a> {
     Gate(Gate<'a>),
 }
 
-pub fn parse(input: &str) -> HashMap<&str, Wire<'_>> {
-    Regex::new(r"([a-z\d]*?) ?(AND|OR|LSHIFT|RSHIFT|NOT|) ?([a-z\d]+) -> ([a-z]+)")
-        .unwrap()
-        .captures_iter(input)
-        .map(|c| c.extract())
-        .map(|(_, [lhs, op, rhs, wire])| {
-            (
-                wire,
-                match op {
-                    "AND" => match lhs.parse() {
-                        Ok(v) => Wire::Gate(Gate::AndConst(v, rhs)),
-                        Err(_) => Wire::Gate(Gate::And(lhs, rhs)),
-                    },
-                    "OR" => Wire::Gate(Gate::Or(lhs, rhs)),
-                    "LSHIFT" => Wire::Gate(Gate::LShift(lhs, rhs.parse().unwrap())),
-                    "RSHIFT" => Wire::Gate(Gate::RShift(lhs, rhs.parse().unwrap())),
-                    "NOT" => Wire::Gate(Gate::Not(rhs)),
-                    _ => match rhs.parse() {
-                        Ok(v) => Wire::Signal(v),
-                        Err(_) => Wire::Gate(Gate::Jumper(rhs)),
-                    },
-                },
-            )
-        })
-        .collect()
-}
-
 fn solve(input: &mut HashMap<&str, Wire<'_>>, wire: &str) -> u16 {
     let v = match input[wire] {
         Wire::Signal(v) => v,
@@ -64,16 +38,52 @@ fn solve(input: &mut HashMap<&str, Wire<'_>>, wire: &str) -> u16 {
     v
 }
 
-pub fn part1(input: &HashMap<&str, Wire>) -> u16 {
-    let mut wires = input.clone();
-    solve(&mut wires, "a")
-}
+pub struct Day07;
+
+#[yaaocr::solution(year = 2015, day = 7)]
+impl Solution for Day07 {
+    type Input<'a> = HashMap<&'a str, Wire<'a>>;
+    type Answer1 = u16;
+    type Answer2 = u16;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        Regex::new(r"([a-z\d]*?) ?(AND|OR|LSHIFT|RSHIFT|NOT|) ?([a-z\d]+) -> ([a-z]+)")
+            .unwrap()
+            .captures_iter(input)
+            .map(|c| c.extract())
+            .map(|(_, [lhs, op, rhs, wire])| {
+                (
+                    wire,
+                    match op {
+                        "AND" => match lhs.parse() {
+                            Ok(v) => Wire::Gate(Gate::AndConst(v, rhs)),
+                            Err(_) => Wire::Gate(Gate::And(lhs, rhs)),
+                        },
+                        "OR" => Wire::Gate(Gate::Or(lhs, rhs)),
+                        "LSHIFT" => Wire::Gate(Gate::LShift(lhs, rhs.parse().unwrap())),
+                        "RSHIFT" => Wire::Gate(Gate::RShift(lhs, rhs.parse().unwrap())),
+                        "NOT" => Wire::Gate(Gate::Not(rhs)),
+                        _ => match rhs.parse() {
+                            Ok(v) => Wire::Signal(v),
+                            Err(_) => Wire::Gate(Gate::Jumper(rhs)),
+                        },
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn part1(input: &Self::Input<'_>) -> u16 {
+        let mut wires = input.clone();
+        solve(&mut wires, "a")
+    }
 
-pub fn part2(input: &HashMap<&str, Wire>) -> u16 {
-    let mut wires = input.clone();
-    let prev_a = solve(&mut wires, "a");
+    fn part2(input: &Self::Input<'_>) -> u16 {
+        let mut wires = input.clone();
+        let prev_a = solve(&mut wires, "a");
 
-    wires = input.clone();
-    *wires.get_mut("b").unwrap() = Wire::Signal(prev_a);
-    solve(&mut wires, "a")
+        wires = input.clone();
+        *wires.get_mut("b").unwrap() = Wire::Signal(prev_a);
+        solve(&mut wires, "a")
+    }
 }
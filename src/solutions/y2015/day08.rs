@@ -2,6 +2,7 @@
  * https://adventofcode.com/2015/day/8
  */
 
+#[yaaocr::solution(year = 2015, day = 8)]
 pub fn parse(input: &str) -> Vec<&str> {
     input.lines().collect()
 }
@@ -11,6 +11,7 @@ use itertools::Itertools;
 type DistMat = Vec<Vec<u64>>;
 type IdCache<'a> = HashMap<&'a str, usize>;
 
+#[yaaocr::solution(year = 2015, day = 9)]
 pub fn parse(input: &str) -> DistMat {
     let lines: Vec<[&str; 5]> = input
         .lines()
@@ -81,3 +82,41 @@ pub fn part2(input: &DistMat) -> u64 {
         .max()
         .unwrap()
 }
+
+/// Held-Karp, finding the best path through each set of cities ending in each city.
+#[yaaocr::solution(year = 2015, day = 9, variant = "held_karp")]
+mod held_karp {
+    use super::DistMat;
+    pub use super::parse;
+
+    fn best_path(dm: &DistMat, better: fn(u64, u64) -> u64) -> u64 {
+        let n = dm.len();
+        let mut best = vec![vec![None; n]; 1 << n];
+        for city in 0..n {
+            best[1 << city][city] = Some(0);
+        }
+
+        for visited in 1..1usize << n {
+            for last in 0..n {
+                let Some(len) = best[visited][last] else {
+                    continue;
+                };
+                for next in (0..n).filter(|&next| visited & (1 << next) == 0) {
+                    let path = len + dm[last][next];
+                    let slot = &mut best[visited | 1 << next][next];
+                    *slot = Some(slot.map_or(path, |other| better(other, path)));
+                }
+            }
+        }
+
+        best[(1 << n) - 1].iter().flatten().copied().reduce(better).unwrap()
+    }
+
+    pub fn part1(input: &DistMat) -> u64 {
+        best_path(input, u64::min)
+    }
+
+    pub fn part2(input: &DistMat) -> u64 {
+        best_path(input, u64::max)
+    }
+}
@@ -25,6 +25,7 @@ fn say(seq: &str) -> String {
     s
 }
 
+#[yaaocr::solution(year = 2015, day = 10)]
 pub fn parse(input: &str) -> (usize, usize) {
     let mut seq = input.trim().to_string();
 
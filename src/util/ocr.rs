@@ -0,0 +1,284 @@
+//! Reads the block letters some puzzles draw on a screen, in the 4x6 and 6x10 alphabets Advent
+//! of Code uses, so those days can answer with text that can be submitted.
+
+use std::fmt;
+
+use crate::{Answer, PartAnswer};
+
+/// Letters 6 pixels tall, with the blank columns on their sides trimmed.
+const SMALL: &[(&str, char)] = &[
+    (".##.\n#..#\n#..#\n####\n#..#\n#..#", 'A'),
+    ("###.\n#..#\n###.\n#..#\n#..#\n###.", 'B'),
+    (".##.\n#..#\n#...\n#...\n#..#\n.##.", 'C'),
+    ("####\n#...\n###.\n#...\n#...\n####", 'E'),
+    ("####\n#...\n###.\n#...\n#...\n#...", 'F'),
+    (".##.\n#..#\n#...\n#.##\n#..#\n.###", 'G'),
+    ("#..#\n#..#\n####\n#..#\n#..#\n#..#", 'H'),
+    ("###\n.#.\n.#.\n.#.\n.#.\n###", 'I'),
+    ("..##\n...#\n...#\n...#\n#..#\n.##.", 'J'),
+    ("#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#", 'K'),
+    ("#...\n#...\n#...\n#...\n#...\n####", 'L'),
+    (".##.\n#..#\n#..#\n#..#\n#..#\n.##.", 'O'),
+    ("###.\n#..#\n#..#\n###.\n#...\n#...", 'P'),
+    ("###.\n#..#\n#..#\n###.\n#.#.\n#..#", 'R'),
+    (".###\n#...\n#...\n.##.\n...#\n###.", 'S'),
+    ("#..#\n#..#\n#..#\n#..#\n#..#\n.##.", 'U'),
+    ("#...#\n#...#\n.#.#.\n..#..\n..#..\n..#..", 'Y'),
+    ("####\n...#\n..#.\n.#..\n#...\n####", 'Z'),
+];
+
+/// Letters 10 pixels tall, with the blank columns on their sides trimmed.
+const LARGE: &[(&str, char)] = &[
+    (
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+        'A',
+    ),
+    (
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+        'B',
+    ),
+    (
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+        'C',
+    ),
+    (
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+        'E',
+    ),
+    (
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+        'F',
+    ),
+    (
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+        'G',
+    ),
+    (
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+        'H',
+    ),
+    (
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+        'J',
+    ),
+    (
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+        'K',
+    ),
+    (
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+        'L',
+    ),
+    (
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+        'N',
+    ),
+    (
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+        'P',
+    ),
+    (
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+        'R',
+    ),
+    (
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+        'X',
+    ),
+    (
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+        'Z',
+    ),
+];
+
+/// Why a picture couldn't be read as letters.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OcrError {
+    /// The lit pixels are neither 6 nor 10 rows tall
+    Height(usize),
+    /// A glyph that isn't in the alphabet, drawn with `#` and `.`. `read` has the letters around
+    /// it, with `?` for glyphs that couldn't be read.
+    UnknownGlyph { glyph: String, read: String },
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OcrError::Height(height) => write!(
+                f,
+                "Letters are 6 or 10 pixels tall, but the picture is {height} pixels tall"
+            ),
+            OcrError::UnknownGlyph { glyph, read } => {
+                write!(f, "Unknown glyph in \"{read}\":\n{glyph}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+/// Reads the letters in a picture drawn with `#` for lit pixels, like `.##.` or `#  #`.
+pub fn ocr(picture: &str) -> Result<String, OcrError> {
+    let rows: Vec<Vec<bool>> = picture
+        .lines()
+        .map(|line| line.chars().map(|c| c == '#' || c == '█').collect())
+        .collect();
+    ocr_bitmap(&rows)
+}
+
+/// Reads the letters in a picture of lit and unlit pixels, one slice per row.
+pub fn ocr_bitmap<R: AsRef<[bool]>>(rows: &[R]) -> Result<String, OcrError> {
+    let lit = |row: &R| row.as_ref().iter().any(|&pixel| pixel);
+    let first_row = rows.iter().position(lit).unwrap_or(0);
+    let last_row = rows.iter().rposition(lit).map_or(0, |row| row + 1);
+    let rows = &rows[first_row..last_row.max(first_row)];
+
+    let alphabet = match rows.len() {
+        6 => SMALL,
+        10 => LARGE,
+        height => return Err(OcrError::Height(height)),
+    };
+
+    let width = rows.iter().map(|row| row.as_ref().len()).max().unwrap_or(0);
+    let pixel = |row: &R, column: usize| row.as_ref().get(column).copied().unwrap_or(false);
+    let blank_column = |column: usize| rows.iter().all(|row| !pixel(row, column));
+
+    // Letters are the runs of columns between blank columns
+    let mut glyphs = vec![];
+    let mut column = 0;
+    while column < width {
+        if blank_column(column) {
+            column += 1;
+            continue;
+        }
+        let start = column;
+        while column < width && !blank_column(column) {
+            column += 1;
+        }
+        let glyph: Vec<String> = rows
+            .iter()
+            .map(|row| {
+                (start..column)
+                    .map(|column| if pixel(row, column) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        glyphs.push(glyph.join("\n"));
+    }
+
+    let letters: Vec<Option<char>> = glyphs
+        .iter()
+        .map(|glyph| {
+            alphabet
+                .iter()
+                .find(|(letter, _)| letter == glyph)
+                .map(|&(_, c)| c)
+        })
+        .collect();
+
+    match letters.iter().position(Option::is_none) {
+        Some(unknown) => Err(OcrError::UnknownGlyph {
+            glyph: glyphs.swap_remove(unknown),
+            read: letters.iter().map(|c| c.unwrap_or('?')).collect(),
+        }),
+        None => Ok(letters.into_iter().flatten().collect()),
+    }
+}
+
+/// Lets a part return what it read, panicking with the unknown glyph when it couldn't.
+impl PartAnswer for Result<String, OcrError> {
+    fn into_answer(self) -> Option<Answer> {
+        match self {
+            Ok(letters) => Some(Answer::Text(letters)),
+            Err(err) => panic!("{err}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_small_letters() {
+        let picture = "\
+.##..###...##..####.####..##..#..#.###...
+#..#.#..#.#..#.#....#....#..#.#..#..#....
+#..#.###..#....###..###..#....####..#....
+####.#..#.#....#....#....#.##.#..#..#....
+#..#.#..#.#..#.#....#....#..#.#..#..#....
+#..#.###...##..####.#.....###.#..#.###...";
+        assert_eq!(ocr(picture), Ok("ABCEFGHI".to_owned()));
+
+        let picture = "\
+..##.#..#.#.....##..###..###...###.#..#.#...#.####
+...#.#.#..#....#..#.#..#.#..#.#....#..#.#...#....#
+...#.##...#....#..#.#..#.#..#.#....#..#..#.#....#.
+...#.#.#..#....#..#.###..###...##..#..#...#....#..
+#..#.#.#..#....#..#.#....#.#.....#.#..#...#...#...
+.##..#..#.####..##..#....#..#.###...##....#...####";
+        assert_eq!(ocr(picture), Ok("JKLOPRSUYZ".to_owned()));
+    }
+
+    #[test]
+    fn reads_large_letters() {
+        let picture = "\
+#....#..######..#####.
+#....#..#.......#....#
+.#..#...#.......#....#
+.#..#...#.......#....#
+..##....#####...#####.
+..##....#.......#..#..
+.#..#...#.......#...#.
+.#..#...#.......#...#.
+#....#..#.......#....#
+#....#..######..#....#";
+        assert_eq!(ocr(picture), Ok("XER".to_owned()));
+    }
+
+    #[test]
+    fn ignores_blank_rows_and_columns_around_letters() {
+        let picture = "
+..........
+...#..#.###....
+...#..#..#.
+...####..#.....
+...#..#..#.
+...#..#..#.
+...#..#.###
+..........";
+        assert_eq!(ocr(picture), Ok("HI".to_owned()));
+    }
+
+    #[test]
+    fn reads_bitmaps() {
+        let rows: Vec<Vec<bool>> = ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]
+            .iter()
+            .map(|row| row.chars().map(|c| c == '#').collect())
+            .collect();
+        assert_eq!(ocr_bitmap(&rows), Ok("H".to_owned()));
+    }
+
+    #[test]
+    fn shows_unknown_glyphs() {
+        let picture = "\
+#..#.####.###
+#..#.#..#..#.
+####.#..#..#.
+#..#.#..#..#.
+#..#.#..#..#.
+#..#.####.###";
+        assert_eq!(
+            ocr(picture),
+            Err(OcrError::UnknownGlyph {
+                glyph: "####\n#..#\n#..#\n#..#\n#..#\n####".to_owned(),
+                read: "H?I".to_owned(),
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_other_heights() {
+        assert_eq!(ocr("#\n#\n#"), Err(OcrError::Height(3)));
+    }
+}
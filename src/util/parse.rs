@@ -42,12 +42,12 @@ pub trait ParseOps {
 impl<S: AsRef<str>> ParseOps for S {
     fn unsigned<T: Unsigned<T>>(&self) -> T {
         let str = self.as_ref();
-        try_unsigned(&mut str.bytes()).expect(format!("Unable to parse \"{str}\"").as_str())
+        try_unsigned(&mut str.bytes()).unwrap_or_else(|| panic!("Unable to parse \"{str}\""))
     }
 
     fn signed<T: Signed<T>>(&self) -> T {
         let str = self.as_ref();
-        try_signed(&mut str.bytes()).expect(format!("Unable to parse \"{str}\"").as_str())
+        try_signed(&mut str.bytes()).unwrap_or_else(|| panic!("Unable to parse \"{str}\""))
     }
 
     fn iter_unsigned<T: Unsigned<T>>(&self) -> ParseUnsigned<'_, T> {
@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Command;
+use std::thread;
+use std::time::Duration;
+use std::{env, fs};
+
+use crate::{
+    Error, check_year_day, example_expected_path, example_path, expected_path, input_path,
+    solution_path,
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Rebuilds and reruns a day whenever its solution, input or examples change, until killed.
+pub fn watch(year: u32, day: u32) -> Result<(), Error> {
+    if !check_year_day(year, day) {
+        return Err(Error::InvalidPuzzle { year, day });
+    }
+
+    let mut mtimes = HashMap::new();
+    let mut first_run = true;
+    loop {
+        let changed: Vec<PathBuf> = watched_paths(year, day)
+            .into_iter()
+            .filter(|path| {
+                let mtime = fs::metadata(path).and_then(|m| m.modified()).ok();
+                mtimes.insert(path.clone(), mtime) != Some(mtime)
+            })
+            .collect();
+
+        if !changed.is_empty() {
+            if !first_run {
+                for path in &changed {
+                    println!("Changed: '{}'", path.display());
+                }
+            }
+            first_run = false;
+
+            rebuild_and_run(year, day)?;
+            println!("Watching for changes, press Ctrl-C to stop");
+            println!();
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn watched_paths(year: u32, day: u32) -> Vec<PathBuf> {
+    let mut paths = vec![
+        solution_path(year, day),
+        input_path(year, day),
+        expected_path(year, day),
+    ];
+    for example in (1..).take_while(|&example| example_path(year, day, example).exists()) {
+        paths.push(example_path(year, day, example));
+        paths.push(example_expected_path(year, day, example));
+    }
+    paths
+}
+
+fn rebuild_and_run(year: u32, day: u32) -> Result<(), Error> {
+    println!("Building...");
+    if !cargo("build").status()?.success() {
+        println!("✗ Build failed");
+        println!();
+        return Ok(());
+    }
+    println!();
+
+    let mut runs = vec![vec!["--verify".to_owned()]];
+    for example in (1..).take_while(|&example| example_path(year, day, example).exists()) {
+        runs.push(vec!["--example".to_owned(), example.to_string()]);
+    }
+
+    for args in runs {
+        cargo("run")
+            .args([
+                "--",
+                "run",
+                "--year",
+                &year.to_string(),
+                "--day",
+                &day.to_string(),
+            ])
+            .args(args)
+            .status()?;
+    }
+
+    Ok(())
+}
+
+/// A cargo command using the same profile as this build.
+fn cargo(subcommand: &str) -> Command {
+    let mut command = Command::new(env::var("CARGO").unwrap_or("cargo".to_owned()));
+    command.args([subcommand, "--quiet"]);
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+    command
+}
@@ -0,0 +1,107 @@
+//! Generates a test for each part of each solution that has an input and an expected answer,
+//! named like `y2015_day07_part2`, so `cargo test y2015` runs every 2015 day. Examples with
+//! expected answers get tests too, named like `y2015_day07_example1_part2`, and so do variants,
+//! named like `y2015_day09_held_karp_part1`.
+
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+use std::thread;
+
+use libtest_mimic::{Arguments, Failed, Trial};
+use yaaocr::expected::Expected;
+use yaaocr::{
+    PartResult, RegisteredSolution, SOLUTION_STACK_SIZE, example_expected_path, example_path,
+    expected_path, filtered_solutions, input_path,
+};
+
+fn main() {
+    let args = Arguments::from_args();
+
+    let mut trials = vec![];
+    for solution in filtered_solutions(None, None) {
+        let (year, day) = (solution.year, solution.day);
+        let name = match solution.variant {
+            Some(variant) => format!("y{year}_day{day:02}_{variant}"),
+            None => format!("y{year}_day{day:02}"),
+        };
+
+        trials.extend(trials_for(
+            &name,
+            solution,
+            input_path(year, day),
+            expected_path(year, day),
+        ));
+
+        for example in (1..).take_while(|&example| example_path(year, day, example).exists()) {
+            trials.extend(trials_for(
+                &format!("{name}_example{example}"),
+                solution,
+                example_path(year, day, example),
+                example_expected_path(year, day, example),
+            ));
+        }
+    }
+
+    libtest_mimic::run(&args, trials).exit();
+}
+
+/// One trial per part with an expected answer. Both trials share a single run of the solution.
+fn trials_for(
+    name: &str,
+    solution: RegisteredSolution,
+    input_path: PathBuf,
+    expected_path: PathBuf,
+) -> Vec<Trial> {
+    if !input_path.exists() {
+        return vec![];
+    }
+    let expected = match Expected::read(&expected_path) {
+        Ok(expected) => expected,
+        Err(err) => {
+            let message = format!("Failed to read '{}': {err}", expected_path.display());
+            return vec![Trial::test(format!("{name}_expected"), move || {
+                Err(message.into())
+            })];
+        }
+    };
+
+    let answers = Arc::new(OnceLock::new());
+    let run = move || -> Result<(PartResult, PartResult), String> {
+        let input = std::fs::read_to_string(&input_path)
+            .map_err(|err| format!("Failed to read '{}': {err}", input_path.display()))?;
+
+        let handle = thread::Builder::new()
+            .stack_size(SOLUTION_STACK_SIZE)
+            .spawn(move || {
+                let (part1, part2, _) = solution.run(&input);
+                (part1, part2)
+            })
+            .map_err(|err| format!("Failed to spawn solution thread: {err}"))?;
+
+        Ok(handle.join().expect("Solutions catch their own panics"))
+    };
+    let run = Arc::new(run);
+
+    [(1, expected.part1), (2, expected.part2)]
+        .into_iter()
+        .filter_map(|(part, expected)| Some((part, expected?)))
+        .map(|(part, expected)| {
+            let (answers, run) = (Arc::clone(&answers), Arc::clone(&run));
+            Trial::test(format!("{name}_part{part}"), move || {
+                let answers = answers.get_or_init(|| run());
+                let (part1, part2) = answers.as_ref().map_err(Failed::from)?;
+                let actual = if part == 1 { part1 } else { part2 };
+
+                match actual {
+                    Ok(Some(actual)) if actual.matches(&expected) => Ok(()),
+                    Ok(Some(actual)) if actual.is_multiline() || expected.is_multiline() => {
+                        Err(format!("expected\n{expected}\ngot\n{actual}").into())
+                    }
+                    Ok(Some(actual)) => Err(format!("expected {expected}, got {actual}").into()),
+                    Ok(None) => Err(format!("expected {expected}, but not implemented").into()),
+                    Err(panic) => Err(format!("solution {panic}").into()),
+                }
+            })
+        })
+        .collect()
+}
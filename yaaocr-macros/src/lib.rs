@@ -0,0 +1,213 @@
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{Item, ItemFn, ItemImpl, ItemMod, LitInt, LitStr, Path, parse_macro_input, parse_quote};
+
+/// Registers a solution for a day, see `yaaocr::solution`.
+#[proc_macro_attribute]
+pub fn solution(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut args_parsed = Args::default();
+    let parser = syn::meta::parser(|meta| args_parsed.parse(meta));
+    parse_macro_input!(args with parser);
+
+    let result = match parse_macro_input!(item as Item) {
+        Item::Impl(item) => register_impl(args_parsed, item),
+        Item::Mod(item) => register_module(args_parsed, item),
+        Item::Fn(item) if item.sig.ident == "parse" && args_parsed.part.is_none() => {
+            register_parse(args_parsed, item)
+        }
+        Item::Fn(item) => register_part(args_parsed, item),
+        item => Err(syn::Error::new_spanned(
+            item,
+            "`solution` can only be used on a `Solution` impl, a module, `parse` or a part function",
+        )),
+    };
+
+    result.unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+#[derive(Default)]
+struct Args {
+    year: Option<LitInt>,
+    day: Option<LitInt>,
+    variant: Option<LitStr>,
+    part: Option<LitInt>,
+    parse: Option<Path>,
+}
+
+impl Args {
+    fn parse(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("year") {
+            self.year = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("day") {
+            self.day = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("variant") {
+            self.variant = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("part") {
+            self.part = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("parse") {
+            self.parse = Some(meta.value()?.parse()?);
+        } else {
+            return Err(meta.error("expected `year`, `day`, `variant`, `part` or `parse`"));
+        }
+        Ok(())
+    }
+
+    /// The variant as an `Option<&'static str>` expression.
+    fn variant(&self) -> proc_macro2::TokenStream {
+        match &self.variant {
+            Some(variant) => quote!(::core::option::Option::Some(#variant)),
+            None => quote!(::core::option::Option::None),
+        }
+    }
+
+    fn year_day(&self) -> syn::Result<(u32, u32)> {
+        let missing = |name| syn::Error::new(Span::call_site(), format!("missing `{name} = ...`"));
+        let year: u32 = self
+            .year
+            .as_ref()
+            .ok_or_else(|| missing("year"))?
+            .base10_parse()?;
+        let day: u32 = self
+            .day
+            .as_ref()
+            .ok_or_else(|| missing("day"))?
+            .base10_parse()?;
+        Ok((year, day))
+    }
+
+    /// Year and day for a registration of both parts, which can't take part arguments.
+    fn whole_day(&self) -> syn::Result<(u32, u32)> {
+        if let Some(arg) = &self.part {
+            return Err(syn::Error::new_spanned(
+                arg,
+                "`part` is only for part functions",
+            ));
+        }
+        if let Some(arg) = &self.parse {
+            return Err(syn::Error::new_spanned(
+                arg,
+                "`parse` is only for part functions",
+            ));
+        }
+        self.year_day()
+    }
+}
+
+/// Registers an implementation of `yaaocr::Solution`.
+fn register_impl(args: Args, item: ItemImpl) -> syn::Result<proc_macro2::TokenStream> {
+    let (year, day) = args.whole_day()?;
+    if item.trait_.is_none() {
+        return Err(syn::Error::new_spanned(
+            item.self_ty,
+            "`solution` can only be used on an `impl Solution for ...`",
+        ));
+    }
+    let self_ty = &item.self_ty;
+    let variant = args.variant();
+
+    Ok(quote! {
+        #item
+
+        ::yaaocr::inventory::submit! {
+            ::yaaocr::Registration {
+                year: #year,
+                day: #day,
+                variant: #variant,
+                wrapper: ::yaaocr::Registered::Day(::yaaocr::run_solution::<#self_ty>),
+            }
+        }
+    })
+}
+
+/// Registers a whole day from the `parse`, `part1` and `part2` functions in an inline module.
+fn register_module(args: Args, mut item: ItemMod) -> syn::Result<proc_macro2::TokenStream> {
+    let (year, day) = args.whole_day()?;
+    let Some((_, items)) = item.content.as_mut() else {
+        return Err(syn::Error::new_spanned(
+            item,
+            "`solution` can only be used on inline modules, use it on `parse` in the module instead",
+        ));
+    };
+
+    items.push(syn::parse2(register_day(year, day, args.variant()))?);
+
+    Ok(quote!(#item))
+}
+
+/// Registers a whole day from `parse` and the `part1` and `part2` functions next to it.
+fn register_parse(args: Args, item: ItemFn) -> syn::Result<proc_macro2::TokenStream> {
+    let (year, day) = args.whole_day()?;
+    let registration = register_day(year, day, args.variant());
+
+    Ok(quote! {
+        #item
+
+        #registration
+    })
+}
+
+fn register_day(
+    year: u32,
+    day: u32,
+    variant: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    quote! {
+        ::yaaocr::inventory::submit! {
+            ::yaaocr::Registration {
+                year: #year,
+                day: #day,
+                variant: #variant,
+                wrapper: ::yaaocr::Registered::Day(|input| {
+                    ::yaaocr::run_day(
+                        input,
+                        |input| parse(input),
+                        |parsed| part1(parsed),
+                        |parsed| part2(parsed),
+                    )
+                }),
+            }
+        }
+    }
+}
+
+/// Registers one part from a function, parsing its input with `parse` from the same module
+/// unless another function is given.
+fn register_part(args: Args, item: ItemFn) -> syn::Result<proc_macro2::TokenStream> {
+    let (year, day) = args.year_day()?;
+
+    let name = &item.sig.ident;
+    let part = match &args.part {
+        Some(part) => part.base10_parse()?,
+        None if name == "part1" => 1,
+        None if name == "part2" => 2,
+        None => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "can't tell which part this is, name it `part1` or `part2` or add `part = ...`",
+            ));
+        }
+    };
+    let kind = match part {
+        1 => quote!(Part1),
+        2 => quote!(Part2),
+        _ => return Err(syn::Error::new_spanned(&args.part, "`part` must be 1 or 2")),
+    };
+    let variant = args.variant();
+    let parse = args.parse.unwrap_or_else(|| parse_quote!(parse));
+
+    Ok(quote! {
+        #item
+
+        ::yaaocr::inventory::submit! {
+            ::yaaocr::Registration {
+                year: #year,
+                day: #day,
+                variant: #variant,
+                wrapper: ::yaaocr::Registered::#kind(|input| {
+                    ::yaaocr::run_part(input, |input| #parse(input), |parsed| #name(parsed))
+                }),
+            }
+        }
+    })
+}